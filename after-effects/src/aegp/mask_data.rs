@@ -0,0 +1,266 @@
+use crate::*;
+use crate::aegp::{ Layer, Mask, MaskFeatherInterp, MaskFeatherType, MaskMode, MaskOutline, MaskStream, PluginId, TimeMode };
+use serde::{ Deserialize, Serialize };
+
+/// A mask vertex, with tangents relative to the vertex. Same as [`ae_sys::AEGP_MaskVertex`].
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct MaskVertexData {
+    pub x: f64,
+    pub y: f64,
+    #[serde(default)]
+    pub tan_in: [f64; 2],
+    #[serde(default)]
+    pub tan_out: [f64; 2],
+}
+
+impl From<ae_sys::AEGP_MaskVertex> for MaskVertexData {
+    fn from(v: ae_sys::AEGP_MaskVertex) -> Self {
+        Self { x: v.x, y: v.y, tan_in: [v.tan_in_x, v.tan_in_y], tan_out: [v.tan_out_x, v.tan_out_y] }
+    }
+}
+
+impl From<MaskVertexData> for ae_sys::AEGP_MaskVertex {
+    fn from(v: MaskVertexData) -> Self {
+        Self { x: v.x, y: v.y, tan_in_x: v.tan_in[0], tan_in_y: v.tan_in[1], tan_out_x: v.tan_out[0], tan_out_y: v.tan_out[1] }
+    }
+}
+
+/// A variable width feather point of a mask outline. Same as [`ae_sys::AEGP_MaskFeather`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MaskFeatherData {
+    /// Index of the segment with the feather point.
+    pub segment: i32,
+    /// Position on the segment, in `0.0..=1.0`.
+    pub segment_position: f64,
+    /// Negative values are allowed for [`MaskFeatherType::Inner`].
+    pub radius: f64,
+    /// Angle of the UI handle on corners, in `0.0..=1.0`.
+    pub corner_angle: f32,
+    /// Tension of the boundary at the feather point, in `0.0..=1.0`.
+    pub tension: f32,
+    pub interp: MaskFeatherInterp,
+    #[serde(rename = "type")]
+    pub feather_type: MaskFeatherType,
+}
+
+impl From<ae_sys::AEGP_MaskFeather> for MaskFeatherData {
+    fn from(f: ae_sys::AEGP_MaskFeather) -> Self {
+        Self {
+            segment:          f.segment,
+            segment_position: f.segment_sF,
+            radius:           f.radiusF,
+            corner_angle:     f.ui_corner_angleF,
+            tension:          f.tensionF,
+            interp:           f.interp.into(),
+            feather_type:     f.type_.into(),
+        }
+    }
+}
+
+impl From<MaskFeatherData> for ae_sys::AEGP_MaskFeather {
+    fn from(f: MaskFeatherData) -> Self {
+        Self {
+            segment:          f.segment,
+            segment_sF:       f.segment_position,
+            radiusF:          f.radius,
+            ui_corner_angleF: f.corner_angle,
+            tensionF:         f.tension,
+            interp:           f.interp.into(),
+            type_:            f.feather_type.into(),
+        }
+    }
+}
+
+/// A layer mask as plain data, to exchange masks with other software as JSON (with `serde`) or as SVG path data.
+///
+/// Read the masks of a layer with [`read_layer()`](Self::read_layer) and add them to a layer with [`add_to_layer()`](Self::add_to_layer).
+/// Missing fields are set to their defaults when deserializing, so `{ "vertices": [{ "x": 0, "y": 0 }, ...] }` is a valid mask.
+///
+/// ```ignore
+/// let masks = aegp::MaskData::read_layer(&layer, plugin_id, time)?;
+/// let json = serde_json::to_string(&masks)?;
+///
+/// let masks: Vec<aegp::MaskData> = serde_json::from_str(&json)?;
+/// aegp::MaskData::add_to_layer(&masks, &other_layer, plugin_id, "Paste Masks")?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MaskData {
+    /// Vertices in layer coordinates. Closed masks don't repeat the first vertex at the end.
+    pub vertices: Vec<MaskVertexData>,
+    pub closed: bool,
+    pub mode: MaskMode,
+    pub inverted: bool,
+    /// Mask feather in pixels, horizontally and vertically.
+    pub feather: [f64; 2],
+    /// Variable width feather points.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub feather_points: Vec<MaskFeatherData>,
+    /// Color of the mask outline in the UI, as red, green and blue in `0.0..=1.0`. `None` keeps the color chosen by After Effects.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<[f64; 3]>,
+}
+
+impl Default for MaskData {
+    fn default() -> Self {
+        Self {
+            vertices: Vec::new(),
+            closed: true,
+            mode: MaskMode::Add,
+            inverted: false,
+            feather: [0.0; 2],
+            feather_points: Vec::new(),
+            color: None,
+        }
+    }
+}
+
+impl MaskData {
+    /// Mask with the shape of `path` and default settings.
+    pub fn from_bezier_path(path: &BezierPath) -> Self {
+        let mut vertices: Vec<MaskVertexData> = Vec::with_capacity(path.segments.len() + 1);
+        for (i, s) in path.segments.iter().enumerate() {
+            let tan_in = match i {
+                0 => [0.0; 2],
+                _ => { let prev = &path.segments[i - 1]; [prev.p2.x - s.p0.x, prev.p2.y - s.p0.y] }
+            };
+            vertices.push(MaskVertexData { x: s.p0.x, y: s.p0.y, tan_in, tan_out: [s.p1.x - s.p0.x, s.p1.y - s.p0.y] });
+        }
+        if let (Some(first), Some(last)) = (path.segments.first(), path.segments.last()) {
+            let tan_in = [last.p2.x - last.p3.x, last.p2.y - last.p3.y];
+            if path.closed && first.p0 == last.p3 {
+                // The closing segment ends at the first vertex
+                vertices[0].tan_in = tan_in;
+            } else {
+                vertices.push(MaskVertexData { x: last.p3.x, y: last.p3.y, tan_in, tan_out: [0.0; 2] });
+            }
+        }
+        Self { vertices, closed: path.closed, ..Default::default() }
+    }
+
+    /// The shape of the mask. Closed masks have a segment from the last vertex back to the first one.
+    pub fn to_bezier_path(&self) -> BezierPath {
+        let vertices: Vec<ae_sys::AEGP_MaskVertex> = self.vertices.iter().map(|&v| v.into()).collect();
+        BezierPath::from_vertices(&vertices, self.closed)
+    }
+
+    /// SVG path data of the mask shape, the `d` attribute of a `<path>`.
+    pub fn to_svg_path_data(&self) -> String {
+        self.to_bezier_path().to_svg_path_data()
+    }
+
+    /// One mask with default settings for each subpath of SVG path data. See [`BezierPath::from_svg_path_data()`].
+    pub fn from_svg_path_data(d: &str) -> Result<Vec<Self>, Error> {
+        Ok(BezierPath::from_svg_path_data(d)?.iter().map(Self::from_bezier_path).collect())
+    }
+
+    /// Read a mask at `time`, in layer time.
+    pub fn read(mask: &Mask, plugin_id: PluginId, time: Time) -> Result<Self, Error> {
+        let stream_suite = aegp::suites::Stream::new()?;
+        let outline_stream = mask.stream(plugin_id, MaskStream::Outline)?;
+        let mut value = stream_suite.new_raw_stream_value(outline_stream.as_ptr(), plugin_id, TimeMode::LayerTime, time, false)?;
+        let outline = Self::read_outline(&MaskOutline::from_raw(unsafe { value.val.mask }));
+        stream_suite.dispose_stream_value(&mut value)?;
+        let (vertices, closed, feather_points) = outline?;
+
+        let feather = mask.stream(plugin_id, MaskStream::Feather)?.new_value(plugin_id, TimeMode::LayerTime, time, false)?;
+        let color = mask.color()?;
+        Ok(Self {
+            vertices,
+            closed,
+            mode: mask.mode()?,
+            inverted: mask.is_inverted()?,
+            feather: feather.try_into()?,
+            feather_points,
+            color: Some([color.redF, color.greenF, color.blueF]),
+        })
+    }
+
+    fn read_outline(outline: &MaskOutline) -> Result<(Vec<MaskVertexData>, bool, Vec<MaskFeatherData>), Error> {
+        let closed = !outline.is_open()?;
+        let num_segments = outline.num_segments()?;
+        let mut vertices = Vec::with_capacity(num_segments as usize + 1);
+        for i in 0..=num_segments {
+            vertices.push(MaskVertexData::from(outline.vertex_info(i)?));
+        }
+        // Closed outlines can end with the first vertex again
+        if closed && vertices.len() > 1 && vertices.first().map(|v| (v.x, v.y)) == vertices.last().map(|v| (v.x, v.y)) {
+            vertices.pop();
+        }
+        let feather_points = (0..outline.num_feathers()?).map(|i| outline.feather_info(i).map(Into::into)).collect::<Result<_, _>>()?;
+        Ok((vertices, closed, feather_points))
+    }
+
+    /// Read all masks of `layer` at `time`, in layer time.
+    pub fn read_layer(layer: &Layer, plugin_id: PluginId, time: Time) -> Result<Vec<Self>, Error> {
+        (0..layer.num_masks()?).map(|i| Self::read(&layer.mask_by_index(i)?, plugin_id, time)).collect()
+    }
+
+    /// Add `masks` to `layer`, in one undo group named `undo_name`. Returns the new masks.
+    ///
+    /// The outline and feather are set as static values, without keyframes.
+    pub fn add_to_layer(masks: &[Self], layer: &Layer, plugin_id: PluginId, undo_name: &str) -> Result<Vec<Mask>, Error> {
+        let utility = aegp::suites::Utility::new()?;
+        utility.start_undo_group(undo_name)?;
+        let result = masks.iter().map(|mask| mask.add_to_layer_inner(layer, plugin_id)).collect();
+        // End the group even if adding a mask failed, so the masks added so far can be undone together
+        utility.end_undo_group()?;
+        result
+    }
+
+    fn add_to_layer_inner(&self, layer: &Layer, plugin_id: PluginId) -> Result<Mask, Error> {
+        let (mask, _index) = layer.create_new_mask()?;
+        mask.set_mode(self.mode)?;
+        mask.set_inverted(self.inverted)?;
+        if let Some([red, green, blue]) = self.color {
+            mask.set_color(pf::PixelF64 { alphaF: 1.0, redF: red, greenF: green, blueF: blue })?;
+        }
+
+        let stream_suite = aegp::suites::Stream::new()?;
+        let outline_stream = mask.stream(plugin_id, MaskStream::Outline)?;
+        let mut value = stream_suite.new_raw_stream_value(outline_stream.as_ptr(), plugin_id, TimeMode::LayerTime, Time { value: 0, scale: 1 }, false)?;
+        let result = self.write_outline(&MaskOutline::from_raw(unsafe { value.val.mask }))
+            .and_then(|_| stream_suite.set_stream_value(outline_stream.as_ptr(), plugin_id, &mut value));
+        stream_suite.dispose_stream_value(&mut value)?;
+        result?;
+
+        let feather_stream = mask.stream(plugin_id, MaskStream::Feather)?;
+        let mut value = ae_sys::AEGP_StreamValue2 {
+            streamH: feather_stream.as_ptr(),
+            val: aegp::StreamValue::TwoD { x: self.feather[0], y: self.feather[1] }.to_sys(),
+        };
+        stream_suite.set_stream_value(feather_stream.as_ptr(), plugin_id, &mut value)?;
+        Ok(mask)
+    }
+
+    fn write_outline(&self, outline: &MaskOutline) -> Result<(), Error> {
+        for (i, vertex) in self.vertices.iter().enumerate() {
+            outline.create_vertex(i as i32)?;
+            outline.set_vertex_info(i as i32, &(*vertex).into())?;
+        }
+        outline.set_open(!self.closed)?;
+        for feather in &self.feather_points {
+            outline.create_feather(Some((*feather).into()))?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_mask_data() {
+    let masks = MaskData::from_svg_path_data("M0 0 C10 0 10 10 10 20 L0 20 Z M5 5 L6 6").unwrap();
+    assert_eq!(masks.len(), 2);
+    assert_eq!(masks[0].vertices, [
+        MaskVertexData { x: 0.0,  y: 0.0,  tan_in: [0.0; 2],    tan_out: [10.0, 0.0] },
+        MaskVertexData { x: 10.0, y: 20.0, tan_in: [0.0, -10.0], tan_out: [0.0; 2] },
+        MaskVertexData { x: 0.0,  y: 20.0, tan_in: [0.0; 2],    tan_out: [0.0; 2] },
+    ]);
+    assert!(masks[0].closed && !masks[1].closed);
+    assert_eq!(masks[1].vertices.len(), 2);
+    assert_eq!(MaskData::from_svg_path_data(&masks[0].to_svg_path_data()).unwrap()[0], masks[0]);
+
+    let json = serde_json::to_string(&masks[0]).unwrap();
+    assert_eq!(serde_json::from_str::<MaskData>(&json).unwrap(), masks[0]);
+    let mask: MaskData = serde_json::from_str(r#"{ "vertices": [{ "x": 1, "y": 2 }], "mode": "subtract" }"#).unwrap();
+    assert_eq!((mask.vertices[0].x, mask.mode, mask.closed), (1.0, MaskMode::Subtract, true));
+}
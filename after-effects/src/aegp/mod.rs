@@ -8,6 +8,9 @@ mod scene_3d;
 #[cfg(feature = "artisan-2-api")]
 pub use scene_3d::*;
 
+mod mask_data;
+pub use mask_data::*;
+
 pub mod suites {
     pub(crate) mod camera;               pub use camera              ::CameraSuite             as Camera;
     pub(crate) mod canvas;               pub use canvas              ::CanvasSuite             as Canvas;
@@ -110,9 +113,11 @@ pub use suites::layer::{
     LayerHandle,
     LayerQuality,
     LayerSamplingQuality,
+    LayerTransferMode,
     ObjectType,
     TimeMode,
     TrackMatte,
+    TransferFlags,
 };
 pub use suites::light::LightType;
 pub use suites::mask::{
@@ -7,7 +7,7 @@ define_suite!(
     CompSuite,
     AEGP_CompSuite11,
     kAEGPCompSuite,
-    kAEGPCompSuiteVersion11
+    [kAEGPCompSuiteVersion11, kAEGPCompSuiteVersion10]
 );
 
 impl CompSuite {
@@ -362,6 +362,7 @@ impl CompSuite {
 
     /// Move the selection to a certain layer index. Use along with [`set_selection()`](Self::set_selection).
     pub fn reorder_comp_selection(&self, comp_handle: impl AsPtr<AEGP_CompH>, layer_index: i32) -> Result<(), Error> {
+        self.require_version(ae_sys::kAEGPCompSuiteVersion11)?;
         call_suite_fn!(self, AEGP_ReorderCompSelection, comp_handle.as_ptr(), layer_index)
     }
 }
@@ -17,21 +17,9 @@ define_suite!(
     /// offset = compIn - stretch * layerIn;
     /// ```
     LayerSuite,
-    AEGP_LayerSuite7,
-    kAEGPLayerSuite,
-    kAEGPLayerSuiteVersion7
-);
-define_suite!(
-    LayerSuite8,
-    AEGP_LayerSuite8,
-    kAEGPLayerSuite,
-    kAEGPLayerSuiteVersion8
-);
-define_suite!(
-    LayerSuite9,
     AEGP_LayerSuite9,
     kAEGPLayerSuite,
-    kAEGPLayerSuiteVersion9
+    [kAEGPLayerSuiteVersion9, kAEGPLayerSuiteVersion8, kAEGPLayerSuiteVersion7]
 );
 
 impl LayerSuite {
@@ -183,7 +171,7 @@ impl LayerSuite {
     }
 
     /// Get transfer mode of a layer.
-    pub fn layer_transfer_mode(&self, layer_handle: impl AsPtr<AEGP_LayerH>) -> Result<ae_sys::AEGP_LayerTransferMode, Error> {
+    pub fn layer_transfer_mode(&self, layer_handle: impl AsPtr<AEGP_LayerH>) -> Result<LayerTransferMode, Error> {
         Ok(call_suite_fn_single!(self, AEGP_GetLayerTransferMode -> ae_sys::AEGP_LayerTransferMode, layer_handle.as_ptr())?.into())
     }
 
@@ -191,8 +179,8 @@ impl LayerSuite {
     ///
     /// As of 23.0, when you make a layer a track matte, the layer being matted will be disabled,
     /// as when you do this via the interface.
-    pub fn set_layer_transfer_mode(&self, layer_handle: impl AsPtr<AEGP_LayerH>, transfer_mode: &ae_sys::AEGP_LayerTransferMode) -> Result<(), Error> {
-        call_suite_fn!(self, AEGP_SetLayerTransferMode, layer_handle.as_ptr(), transfer_mode)
+    pub fn set_layer_transfer_mode(&self, layer_handle: impl AsPtr<AEGP_LayerH>, transfer_mode: LayerTransferMode) -> Result<(), Error> {
+        call_suite_fn!(self, AEGP_SetLayerTransferMode, layer_handle.as_ptr(), &transfer_mode.into())
     }
 
     /// Tests whether it's currently valid to add a given item to a composition.
@@ -344,8 +332,8 @@ impl LayerSuite {
     /// - [`LayerSamplingQuality::Bilinear`]
     /// - [`LayerSamplingQuality::Bicubic`]
     pub fn layer_sampling_quality(&self, layer_handle: impl AsPtr<AEGP_LayerH>) -> Result<LayerSamplingQuality, Error> {
-        let v8 = LayerSuite8::new()?;
-        Ok(call_suite_fn_single!(v8, AEGP_GetLayerSamplingQuality -> ae_sys::AEGP_LayerSamplingQuality, layer_handle.as_ptr())?.into())
+        self.require_version(ae_sys::kAEGPLayerSuiteVersion8)?;
+        Ok(call_suite_fn_single!(self, AEGP_GetLayerSamplingQuality -> ae_sys::AEGP_LayerSamplingQuality, layer_handle.as_ptr())?.into())
     }
 
     /// New in CC. Sets the sampling quality of a layer (see flag values above).
@@ -355,14 +343,14 @@ impl LayerSuite {
     /// If you want to force it on you must also set the layer quality to [`LayerQuality::Best`] with [`Self::set_layer_quality`].
     /// Otherwise it will only be using the specified layer sampling quality whenever the layer quality is set to [`LayerQuality::Best`].
     pub fn set_layer_sampling_quality(&self, layer_handle: impl AsPtr<AEGP_LayerH>, quality: LayerSamplingQuality) -> Result<(), Error> {
-        let v8 = LayerSuite8::new()?;
-        call_suite_fn!(v8, AEGP_SetLayerSamplingQuality, layer_handle.as_ptr(), quality.into())
+        self.require_version(ae_sys::kAEGPLayerSuiteVersion8)?;
+        call_suite_fn!(self, AEGP_SetLayerSamplingQuality, layer_handle.as_ptr(), quality.into())
     }
 
     /// New in 23.0. Returns the track matte layer of [`LayerHandle`]. Returns `None` if there is no track matte layer.
     pub fn track_matte_layer(&self, layer_handle: impl AsPtr<AEGP_LayerH>) -> Result<Option<LayerHandle>, Error> {
-        let v9 = LayerSuite9::new()?;
-        let track_matte_handle = call_suite_fn_single!(v9, AEGP_GetTrackMatteLayer -> ae_sys::AEGP_LayerH, layer_handle.as_ptr())?;
+        self.require_version(ae_sys::kAEGPLayerSuiteVersion9)?;
+        let track_matte_handle = call_suite_fn_single!(self, AEGP_GetTrackMatteLayer -> ae_sys::AEGP_LayerH, layer_handle.as_ptr())?;
         if track_matte_handle.is_null() {
             Ok(None)
         } else {
@@ -374,14 +362,14 @@ impl LayerSuite {
     ///
     /// Setting the track matte type as [`TrackMatte::NoTrackMatte`] removes track matte.
     pub fn set_track_matte(&self, layer_handle: impl AsPtr<AEGP_LayerH>, track_matte_layer: Option<LayerHandle>, track_matte_type: TrackMatte) -> Result<(), Error> {
-        let v9 = LayerSuite9::new()?;
-        call_suite_fn!(v9, AEGP_SetTrackMatte, layer_handle.as_ptr(), track_matte_layer.map_or(std::ptr::null_mut(), |h| h.as_ptr()), track_matte_type.into())
+        self.require_version(ae_sys::kAEGPLayerSuiteVersion9)?;
+        call_suite_fn!(self, AEGP_SetTrackMatte, layer_handle.as_ptr(), track_matte_layer.map_or(std::ptr::null_mut(), |h| h.as_ptr()), track_matte_type.into())
     }
 
     /// New in 23.0. Removes the track matte layer of [`LayerHandle`].
     pub fn remove_track_matte(&self, layer_handle: impl AsPtr<AEGP_LayerH>) -> Result<(), Error> {
-        let v9 = LayerSuite9::new()?;
-        call_suite_fn!(v9, AEGP_RemoveTrackMatte, layer_handle.as_ptr())
+        self.require_version(ae_sys::kAEGPLayerSuiteVersion9)?;
+        call_suite_fn!(self, AEGP_RemoveTrackMatte, layer_handle.as_ptr())
     }
 }
 
@@ -431,6 +419,41 @@ define_enum! {
     }
 }
 
+bitflags::bitflags! {
+    #[derive(Copy, Clone, Debug)]
+    pub struct TransferFlags: ae_sys::A_long {
+        const PRESERVE_ALPHA     = ae_sys::AEGP_TransferFlag_PRESERVE_ALPHA     as ae_sys::A_long;
+        const RANDOMIZE_DISSOLVE = ae_sys::AEGP_TransferFlag_RANDOMIZE_DISSOLVE as ae_sys::A_long;
+    }
+}
+
+/// Transfer mode of a layer. The `mode` can be reproduced with [`pf::blend`](crate::pf::blend).
+#[derive(Debug, Clone, Copy)]
+pub struct LayerTransferMode {
+    pub mode: pf::TransferMode,
+    pub flags: TransferFlags,
+    pub track_matte: TrackMatte,
+}
+
+impl From<ae_sys::AEGP_LayerTransferMode> for LayerTransferMode {
+    fn from(mode: ae_sys::AEGP_LayerTransferMode) -> Self {
+        Self {
+            mode: mode.mode.into(),
+            flags: TransferFlags::from_bits_truncate(mode.flags as _),
+            track_matte: mode.track_matte.into(),
+        }
+    }
+}
+impl From<LayerTransferMode> for ae_sys::AEGP_LayerTransferMode {
+    fn from(mode: LayerTransferMode) -> Self {
+        Self {
+            mode: mode.mode.into(),
+            flags: mode.flags.bits() as _,
+            track_matte: mode.track_matte.into(),
+        }
+    }
+}
+
 define_enum! {
     ae_sys::AEGP_LTimeMode,
     TimeMode {
@@ -547,13 +570,13 @@ define_suite_item_wrapper!(
         set_stretch(stretch: Ratio) -> () => suite.set_layer_stretch,
 
         /// Get transfer mode of a layer.
-        transfer_mode() -> ae_sys::AEGP_LayerTransferMode => suite.layer_transfer_mode,
+        transfer_mode() -> LayerTransferMode => suite.layer_transfer_mode,
 
         /// Set transfer mode of a layer. Undoable.
         ///
         /// As of 23.0, when you make a layer a track matte, the layer being matted will be disabled,
         /// as when you do this via the interface.
-        set_transfer_mode(transfer_mode: &ae_sys::AEGP_LayerTransferMode) -> () => suite.set_layer_transfer_mode,
+        set_transfer_mode(transfer_mode: LayerTransferMode) -> () => suite.set_layer_transfer_mode,
 
         /// Change the order of layers. Undoable.
         ///
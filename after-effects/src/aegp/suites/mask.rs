@@ -252,6 +252,8 @@ impl Drop for MaskRefHandle {
 
 define_enum! {
     ae_sys::PF_MaskMode,
+    #[derive(serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "snake_case")]
     MaskMode {
         None       = ae_sys::PF_MaskMode_NONE,
         Add        = ae_sys::PF_MaskMode_ADD,
@@ -282,6 +284,8 @@ define_enum! {
 }
 define_enum! {
     ae_sys::AEGP_MaskFeatherInterp,
+    #[derive(serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "snake_case")]
     MaskFeatherInterp {
         Normal  = ae_sys::AEGP_MaskFeatherInterp_NORMAL,
         HoldCW  = ae_sys::AEGP_MaskFeatherInterp_HOLD_CW,
@@ -289,6 +293,8 @@ define_enum! {
 }
 define_enum! {
     ae_sys::AEGP_MaskFeatherType,
+    #[derive(serde::Serialize, serde::Deserialize)]
+    #[serde(rename_all = "snake_case")]
     MaskFeatherType {
         Outer = ae_sys::AEGP_MaskFeatherType_OUTER,
         Inner = ae_sys::AEGP_MaskFeatherType_INNER,
@@ -130,19 +130,27 @@ impl StreamSuite {
     pub fn new_stream_value(&self, stream_ref: impl AsPtr<AEGP_StreamRefH>, plugin_id: PluginId, time_mode: TimeMode, time: Time, sample_stream_pre_expression: bool) -> Result<StreamValue, Error> {
         let type_ = self.stream_type(stream_ref.as_ptr())?;
 
-        let mut stream_value2 = call_suite_fn_single!(self,
+        let mut stream_value2 = self.new_raw_stream_value(stream_ref.as_ptr(), plugin_id, time_mode, time, sample_stream_pre_expression)?;
+        let value = StreamValue::from_sys(type_, stream_value2.val);
+
+        self.dispose_stream_value(&mut stream_value2)?;
+
+        Ok(value)
+    }
+
+    /// Like [`new_stream_value()`](Self::new_stream_value), but returns the value owned by After Effects,
+    /// for example to edit a mask outline in place and set it with [`set_stream_value()`](Self::set_stream_value).
+    ///
+    /// The value must be disposed with [`dispose_stream_value()`](Self::dispose_stream_value).
+    pub fn new_raw_stream_value(&self, stream_ref: impl AsPtr<AEGP_StreamRefH>, plugin_id: PluginId, time_mode: TimeMode, time: Time, sample_stream_pre_expression: bool) -> Result<ae_sys::AEGP_StreamValue2, Error> {
+        call_suite_fn_single!(self,
             AEGP_GetNewStreamValue -> ae_sys::AEGP_StreamValue2,
             plugin_id,
             stream_ref.as_ptr(),
             time_mode.into(),
             &time.into() as *const _,
             sample_stream_pre_expression as u8
-        )?;
-        let value = StreamValue::from_sys(type_, stream_value2.val);
-
-        self.dispose_stream_value(&mut stream_value2)?;
-
-        Ok(value)
+        )
     }
 
     /// Dispose of stream value. Always deallocate values passed to the plug-in.
@@ -150,6 +158,13 @@ impl StreamSuite {
         call_suite_fn!(self, AEGP_DisposeStreamValue, stream_value)
     }
 
+    /// Sets the value of a stream. Only valid for streams without keyframes. Undoable.
+    ///
+    /// `value.streamH` is ignored, the value is set on `stream_ref`.
+    pub fn set_stream_value(&self, stream_ref: impl AsPtr<AEGP_StreamRefH>, plugin_id: PluginId, value: &mut ae_sys::AEGP_StreamValue2) -> Result<(), Error> {
+        call_suite_fn!(self, AEGP_SetStreamValue, plugin_id, stream_ref.as_ptr(), value)
+    }
+
     /// NOTE: This convenience function is only valid for streams with primitive data types, and not for `StreamType::ArbBlock`, `StreamType::Marker` or `StreamType::MaskOutline`.
     /// For these and other complex types, use [`new_stream_value()`](Self::new_stream_value), described above.
     pub fn layer_stream_value(&self, layer_handle: impl AsPtr<AEGP_LayerH>, stream: LayerStream, time_mode: TimeMode, time: Time, pre_expression: bool) -> Result<StreamValue, Error> {
@@ -2,13 +2,11 @@
 
 #![doc = include_str!(concat!("../", std::env!("CARGO_PKG_README")))]
 use after_effects_sys as ae_sys;
-use num_traits::identities::Zero;
 use std::{
     cell::RefCell,
     cmp::{max, min, PartialEq, PartialOrd},
     error,
     fmt::Display,
-    ops::{Add, RemAssign},
     ptr,
 };
 #[cfg(feature = "ultraviolet")]
@@ -28,6 +26,11 @@ pub mod aeio;
 pub mod drawbot;
 pub mod pf;
 pub use pf::*;
+mod time;
+pub use time::*;
+mod suite_cache;
+mod versioned_data;
+pub use versioned_data::*;
 pub mod pr;
 pub mod pr_string;
 use pr_string::*;
@@ -69,6 +72,9 @@ pub(crate) fn borrow_pica_basic_as_ptr() -> *const ae_sys::SPBasicSuite {
 /// previous value to our thread-local storage so the caller
 /// can continue using their pointer to the suite.
 ///
+/// Suites are cached per thread while a scope is active, and released
+/// when the outermost scope ends.
+///
 /// FIXME: Is this really neccessary? Check if the pointer is always the
 ///        same and if so, confirm with Adobe we can get rid of it.
 pub struct PicaBasicSuite {
@@ -82,6 +88,7 @@ impl PicaBasicSuite {
         PICA_BASIC_SUITE.with(|pica_basic_ptr_cell| {
             previous_pica_basic_suite_ptr = pica_basic_ptr_cell.replace(pica_basic_suite_ptr);
         });
+        suite_cache::enter_scope();
 
         previous_pica_basic_suite_ptr
     }
@@ -123,15 +130,25 @@ impl Drop for PicaBasicSuite {
         PICA_BASIC_SUITE.with(|pica_basic_ptr_cell| {
             pica_basic_ptr_cell.replace(self.previous_pica_basic_suite_ptr);
         });
+        // Suites acquired in this scope are released when the outermost scope ends
+        suite_cache::leave_scope();
     }
 }
 
 // Error 10007 was Seen in the wild in AE2019
-#[cfg(target_os = "macos")]
+#[cfg(not(target_os = "windows"))]
 const UNKNOWN_ERR_10007: ::std::os::raw::c_uint = 10007;
 #[cfg(target_os = "windows")]
 const UNKNOWN_ERR_10007: ::std::os::raw::c_int = 10007;
 
+// Not an After Effects error, returned by suite methods which need a newer suite version than the host has.
+// The host doesn't know this PF_Err (0x55424821), but it still reaches the host if a plugin propagates it from an entry point,
+// so plugins should map it to an error known by the host before returning.
+#[cfg(not(target_os = "windows"))]
+const UNSUPPORTED_BY_HOST: ::std::os::raw::c_uint = 0x5542_4821;
+#[cfg(target_os = "windows")]
+const UNSUPPORTED_BY_HOST: ::std::os::raw::c_int = 0x5542_4821;
+
 define_enum! {
     ae_sys::PF_Err,
     Error {
@@ -167,6 +184,9 @@ define_enum! {
 
         Unknown10007             = UNKNOWN_ERR_10007,
 
+        // The host doesn't have the suite version needed for this call.
+        UnsupportedByHost        = UNSUPPORTED_BY_HOST,
+
         None = ae_sys::PF_Err_NONE,
     }
 }
@@ -194,6 +214,7 @@ impl From<Error> for &'static str {
             Error::InvalidParms             => "InvalidParms",
             Error::Reserved11               => "Reserved11",
             Error::Unknown10007             => "Unknown10007",
+            Error::UnsupportedByHost        => "UnsupportedByHost",
         }
     }
 }
@@ -236,6 +257,55 @@ impl From<ae_sys::A_Matrix3> for Matrix3 {
     }
 }
 
+/// After Effects 2D matrices transform row vectors: `[x', y', w] = [x, y, 1] * M`, so the translation is in the last row.
+impl Matrix3 {
+    pub const IDENTITY: Self = Self([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]);
+
+    pub fn from_rows(rows: [[f64; 3]; 3]) -> Self {
+        Self(rows)
+    }
+    pub fn rows(&self) -> [[f64; 3]; 3] {
+        self.0
+    }
+    pub fn translate(x: f64, y: f64) -> Self {
+        Self([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [x, y, 1.0]])
+    }
+    pub fn scale(x: f64, y: f64) -> Self {
+        Self([[x, 0.0, 0.0], [0.0, y, 0.0], [0.0, 0.0, 1.0]])
+    }
+    /// Rotation by `radians`, clockwise in layer space (where y points down).
+    pub fn rotate(radians: f64) -> Self {
+        let (sin, cos) = radians.sin_cos();
+        Self([[cos, sin, 0.0], [-sin, cos, 0.0], [0.0, 0.0, 1.0]])
+    }
+
+    /// The transformation of `self` followed by `other`.
+    pub fn then(&self, other: &Matrix3) -> Self {
+        Self(std::array::from_fn(|r| std::array::from_fn(|c| (0..3).map(|i| self.0[r][i] * other.0[i][c]).sum())))
+    }
+
+    /// Returns `None` if the matrix isn't invertible.
+    pub fn inverse(&self) -> Option<Self> {
+        let m = &self.0;
+        let cofactor = |r0: usize, r1: usize, c0: usize, c1: usize| m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0];
+        let det = m[0][0] * cofactor(1, 2, 1, 2) - m[0][1] * cofactor(1, 2, 0, 2) + m[0][2] * cofactor(1, 2, 0, 1);
+        if det.abs() < f64::EPSILON {
+            return None;
+        }
+        Some(Self([
+            [ cofactor(1, 2, 1, 2) / det, -cofactor(0, 2, 1, 2) / det,  cofactor(0, 1, 1, 2) / det],
+            [-cofactor(1, 2, 0, 2) / det,  cofactor(0, 2, 0, 2) / det, -cofactor(0, 1, 0, 2) / det],
+            [ cofactor(1, 2, 0, 1) / det, -cofactor(0, 2, 0, 1) / det,  cofactor(0, 1, 0, 1) / det],
+        ]))
+    }
+
+    pub fn transform_point(&self, x: f64, y: f64) -> (f64, f64) {
+        let m = &self.0;
+        let w = x * m[0][2] + y * m[1][2] + m[2][2];
+        ((x * m[0][0] + y * m[1][0] + m[2][0]) / w, (x * m[0][1] + y * m[1][1] + m[2][1]) / w)
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
 #[repr(C)]
 pub struct Matrix4([[f64; 4]; 4]);
@@ -316,108 +386,6 @@ fn test_from() {
 
 pub type Color = ae_sys::A_Color;
 
-define_struct! {
-    ae_sys::A_Time,
-    #[derive(Eq)]
-    Time {
-        value: i32,
-        scale: u32,
-    }
-}
-impl From<Time> for f64 {
-    #[inline]
-    fn from(time: Time) -> Self {
-        debug_assert!(time.scale != 0);
-        time.value as Self / time.scale as Self
-    }
-}
-impl From<Time> for f32 {
-    #[inline]
-    fn from(time: Time) -> Self {
-        debug_assert!(time.scale != 0);
-        time.value as Self / time.scale as Self
-    }
-}
-
-// Euclid's two-thousand-year-old algorithm for finding the
-// greatest common divisor. Copied non-recursive version from
-// Rust docs.
-#[inline]
-fn greatest_common_divisor<T>(mut n: T, mut m: T) -> T
-where
-    T: Copy + PartialEq + PartialOrd + RemAssign + Zero,
-{
-    debug_assert!(n != Zero::zero() && m != Zero::zero());
-    while m != Zero::zero() {
-        if m < n {
-            std::mem::swap(&mut m, &mut n);
-        }
-        m %= n;
-    }
-    n
-}
-
-/// Calculates the wrapped sum of two [`Time`]s. If no common integer
-/// demoninator can be found, `None` is returned.
-#[inline]
-fn add_time_lossless(time1: Time, time2: Time) -> Option<Time> {
-    if (time1.scale == 0) || (time2.scale == 0) {
-        return None;
-    }
-
-    let gcd = {
-        if time1.scale == time2.scale {
-            time1.scale
-        } else {
-            greatest_common_divisor(time1.scale, time2.scale)
-        }
-    };
-
-    let value1 = time1
-        .value
-        .checked_mul(time2.scale.checked_div(gcd)? as i32)?;
-    let value2 = time2
-        .value
-        .checked_mul(time1.scale.checked_div(gcd)? as i32)?;
-
-    Some(Time {
-        value: value1.checked_add(value2)?,
-        scale: time2.scale.checked_mul(time1.scale.checked_div(gcd)?)?,
-    })
-}
-
-/// Calculates the sum of two [`Time`]s using floating point math.
-#[inline]
-fn add_time_lossy(time1: Time, time2: Time) -> Time {
-    let time =
-        (time1.value as f64 / time1.scale as f64) + (time2.value as f64 / time2.scale as f64);
-
-    let num_bits = time.log2() as usize;
-    let scale: u32 = 1u32 << (30 - num_bits);
-
-    Time {
-        value: (time * scale as f64) as i32,
-        scale,
-    }
-}
-
-// Next bit (std::ops::Add) ported from aeutility.cpp
-// Rust version is so much shorter & cleaner!
-//
-/// Calculates the sum of two [`Time`]s, lossless if possible.
-// FIXME: is it worth going the lossless route at all???
-impl Add<Time> for Time {
-    type Output = Self;
-
-    #[inline]
-    fn add(self, rhs: Self) -> Self {
-        match add_time_lossless(self, rhs) {
-            Some(time) => time,
-            None => add_time_lossy(self, rhs),
-        }
-    }
-}
-
 define_struct! {
     ae_sys::A_LRect,
     #[derive(Eq)]
@@ -522,30 +490,6 @@ impl FloatRect {
     }
 }
 
-define_struct! {
-    ae_sys::A_Ratio,
-    Ratio {
-        num: i32,
-        den: u32,
-    }
-}
-
-impl From<Ratio> for f64 {
-    #[inline]
-    fn from(ratio: Ratio) -> Self {
-        debug_assert!(ratio.den != 0);
-        ratio.num as Self / ratio.den as Self
-    }
-}
-
-impl From<Ratio> for f32 {
-    #[inline]
-    fn from(ratio: Ratio) -> Self {
-        debug_assert!(ratio.den != 0);
-        ratio.num as Self / ratio.den as Self
-    }
-}
-
 pub enum Ownership<'a, T: Clone> {
     AfterEffects(&'a T),
     AfterEffectsMut(&'a mut T),
@@ -1,3 +1,4 @@
+#[cfg(feature = "artisan-2-api")]
 macro_rules! ae_acquire_suite_ptr {
     ($pica:expr, $type:ident, $name:ident, $version:ident) => {{
         unsafe {
@@ -5,7 +6,7 @@ macro_rules! ae_acquire_suite_ptr {
 
             let aquire_suite_func = (*($pica)).AcquireSuite.unwrap_or_else(|| unreachable!());
             match aquire_suite_func(
-                after_effects_sys::$name.as_ptr() as *const i8,
+                after_effects_sys::$name.as_ptr() as *const std::ffi::c_char,
                 after_effects_sys::$version as i32,
                 suite_ptr.as_mut_ptr() as *mut *const _ as _,
             ) as u32
@@ -20,12 +21,13 @@ macro_rules! ae_acquire_suite_ptr {
     }};
 }
 
+#[cfg(feature = "artisan-2-api")]
 macro_rules! ae_release_suite_ptr {
     ($pica:expr, $name:ident, $version:ident) => {{
         unsafe {
             let release_suite_func = (*($pica)).ReleaseSuite.unwrap_or_else(|| unreachable!());
             release_suite_func(
-                after_effects_sys::$name.as_ptr() as *const i8,
+                after_effects_sys::$name.as_ptr() as *const std::ffi::c_char,
                 after_effects_sys::$version as i32,
             );
         }
@@ -486,47 +488,82 @@ macro_rules! define_enum {
     };
 }
 
+/// Defines a suite wrapper.
+///
+/// The version can be a single version, or a list of acceptable versions, newest first. `new()` acquires the newest one the host has,
+/// and [`version()`](Self::version) reports which one it was. Methods which need a newer version than the acquired one
+/// should check it with `require_version()`, which returns [`Error::UnsupportedByHost`].
+///
+/// All versions in the list must be prefixes of `$suite_name`, i.e. newer versions only append functions.
+///
+/// Suite pointers are cached per thread, see `suite_cache`, so constructing, cloning and dropping the wrapper is cheap.
 macro_rules! define_suite {
     ($(#[$attr:meta])* $suite_pretty_name:ident, $suite_name:ident, $suite_name_string:ident, $suite_version:ident) => {
+        define_suite!($(#[$attr])* $suite_pretty_name, $suite_name, $suite_name_string, [$suite_version]);
+    };
+    ($(#[$attr:meta])* $suite_pretty_name:ident, $suite_name:ident, $suite_name_string:ident, [$($suite_version:ident),+ $(,)?]) => {
         #[derive(Debug, Hash)]
         $(#[$attr])*
         pub struct $suite_pretty_name {
             pica_basic_suite_ptr: *const after_effects_sys::SPBasicSuite,
             suite_ptr: *const after_effects_sys::$suite_name,
+            version: i32,
         }
 
         impl Suite for $suite_pretty_name {
             fn new() -> Result<Self, Error> {
                 let pica_basic_suite_ptr = borrow_pica_basic_as_ptr();
 
-                match ae_acquire_suite_ptr!(
-                    pica_basic_suite_ptr,
-                    $suite_name,
-                    $suite_name_string,
-                    $suite_version
-                ) {
-                    Ok(suite_ptr) => Ok(Self {
-                        pica_basic_suite_ptr,
-                        suite_ptr,
-                    }),
-                    Err(e) => Err(e),
+                for version in Self::VERSIONS {
+                    if let Ok(suite_ptr) = crate::suite_cache::acquire(pica_basic_suite_ptr, after_effects_sys::$suite_name_string, *version) {
+                        return Ok(Self {
+                            pica_basic_suite_ptr,
+                            suite_ptr: suite_ptr as *const _,
+                            version: *version,
+                        });
+                    }
+                }
+                log::error!("Suite not found: {} {} {:?}", stringify!($suite_name), stringify!($suite_name_string), Self::VERSIONS);
+                Err(Error::MissingSuite)
+            }
+        }
+
+        impl $suite_pretty_name {
+            /// Acceptable versions, newest first.
+            const VERSIONS: &'static [i32] = &[$( after_effects_sys::$suite_version as i32 ),+];
+
+            /// The suite version acquired from the host, for example `kAEGPLayerSuiteVersion9`.
+            #[allow(dead_code)]
+            pub fn version(&self) -> i32 {
+                self.version
+            }
+
+            /// Returns [`Error::UnsupportedByHost`] if the acquired version is older than `version`.
+            #[allow(dead_code)]
+            pub(crate) fn require_version(&self, version: u32) -> Result<(), Error> {
+                let position = |v: i32| Self::VERSIONS.iter().position(|x| *x == v);
+                match (position(self.version), position(version as i32)) {
+                    (Some(acquired), Some(required)) if acquired <= required => Ok(()),
+                    _ => Err(Error::UnsupportedByHost),
                 }
             }
         }
 
         impl Clone for $suite_pretty_name {
             fn clone(&self) -> Self {
-                Suite::new().unwrap()
+                // Can't fail, the pointer is cached until this one is dropped
+                let suite_ptr = crate::suite_cache::acquire(self.pica_basic_suite_ptr, after_effects_sys::$suite_name_string, self.version).unwrap();
+                Self {
+                    pica_basic_suite_ptr: self.pica_basic_suite_ptr,
+                    suite_ptr: suite_ptr as *const _,
+                    version: self.version,
+                }
             }
         }
 
         impl Drop for $suite_pretty_name {
             fn drop(&mut self) {
-                ae_release_suite_ptr!(
-                    self.pica_basic_suite_ptr,
-                    $suite_name_string,
-                    $suite_version
-                );
+                crate::suite_cache::release(self.pica_basic_suite_ptr, after_effects_sys::$suite_name_string, self.version);
             }
         }
     };
@@ -0,0 +1,163 @@
+use super::*;
+
+// struct PF_SoundFormatInfo {
+//     pub rateF: PF_FpLong,
+//     pub num_channels: PF_SoundChannels,
+//     pub format: PF_SoundFormat,
+//     pub sample_size: PF_SoundSampleSize,
+// }
+// struct PF_SoundWorld {
+//     pub fi: PF_SoundFormatInfo,
+//     pub num_samples: A_long,
+//     pub dataP: *mut c_void,
+// }
+
+define_enum! {
+    ae_sys::PF_SoundFormat,
+    SoundFormat {
+        UnsignedPcm = ae_sys::PF_UNSIGNED_PCM,
+        SignedPcm   = ae_sys::PF_SIGNED_PCM,
+        SignedFloat = ae_sys::PF_SIGNED_FLOAT,
+    }
+}
+
+define_enum! {
+    ae_sys::PF_SoundChannels,
+    SoundChannels {
+        Mono   = ae_sys::PF_Channels_MONO,
+        Stereo = ae_sys::PF_Channels_STEREO,
+    }
+}
+
+/// Describes the samples of a sound buffer. Samples of all channels are interleaved.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SoundFormatInfo {
+    pub sample_rate: f64,
+    pub channels: SoundChannels,
+    pub format: SoundFormat,
+    /// Size of a single sample of a single channel, in bytes.
+    pub sample_size: usize,
+}
+
+impl From<ae_sys::PF_SoundFormatInfo> for SoundFormatInfo {
+    fn from(fi: ae_sys::PF_SoundFormatInfo) -> Self {
+        Self {
+            sample_rate: fi.rateF,
+            channels: fi.num_channels.into(),
+            format: fi.format.into(),
+            sample_size: fi.sample_size as usize,
+        }
+    }
+}
+
+/// Audio buffers passed to [`Command::AudioSetup`] and [`Command::AudioRender`].
+///
+/// The input sound is read from `in_data.src_snd` and the result is written to `out_data.dest_snd`.
+/// Set [`OutFlags::AudioFloatOnly`] during [`Command::GlobalSetup`] to always receive 32-bit float audio,
+/// which is required by [`input()`](Self::input) and [`output()`](Self::output).
+///
+/// This isn't `Clone` or `Copy`, because the output samples can only be borrowed mutably once.
+#[derive(Debug)]
+pub struct AudioRenderExtra {
+    pub(crate) in_data_ptr: *const ae_sys::PF_InData,
+    pub(crate) out_data_ptr: *mut ae_sys::PF_OutData,
+}
+
+impl AudioRenderExtra {
+    pub(crate) fn from_raw(in_data_ptr: *const ae_sys::PF_InData, out_data_ptr: *mut ae_sys::PF_OutData) -> Self {
+        assert!(!in_data_ptr.is_null());
+        assert!(!out_data_ptr.is_null());
+        Self { in_data_ptr, out_data_ptr }
+    }
+
+    fn in_data(&self) -> &ae_sys::PF_InData {
+        unsafe { &*self.in_data_ptr }
+    }
+    fn out_data(&self) -> &ae_sys::PF_OutData {
+        unsafe { &*self.out_data_ptr }
+    }
+    fn out_data_mut(&mut self) -> &mut ae_sys::PF_OutData {
+        unsafe { &mut *self.out_data_ptr }
+    }
+
+    /// Starting sample number, relative to the start of the audio layer.
+    pub fn start_sample(&self) -> i32 {
+        self.in_data().start_sampL
+    }
+    /// Duration of the requested audio, in samples.
+    pub fn duration_samples(&self) -> i32 {
+        self.in_data().dur_sampL
+    }
+    /// Number of samples in the audio layer.
+    pub fn total_samples(&self) -> i32 {
+        self.in_data().total_sampL
+    }
+
+    /// During [`Command::AudioSetup`], request input audio starting at a different sample than the output.
+    pub fn set_input_start_sample(&mut self, start: i32) {
+        self.out_data_mut().start_sampL = start;
+    }
+    /// During [`Command::AudioSetup`], request a different duration of input audio than the output.
+    pub fn set_input_duration_samples(&mut self, duration: i32) {
+        self.out_data_mut().dur_sampL = duration;
+    }
+
+    /// Format of the input sound.
+    pub fn input_format(&self) -> SoundFormatInfo {
+        self.in_data().src_snd.fi.into()
+    }
+    /// Format of the output sound.
+    pub fn output_format(&self) -> SoundFormatInfo {
+        self.out_data().dest_snd.fi.into()
+    }
+    /// Sample rate of the output sound, in Hz.
+    pub fn sample_rate(&self) -> f64 {
+        self.out_data().dest_snd.fi.rateF
+    }
+    /// Channel layout of the output sound.
+    pub fn channels(&self) -> SoundChannels {
+        self.out_data().dest_snd.fi.num_channels.into()
+    }
+
+    /// Interleaved input samples.
+    ///
+    /// Returns [`Error::BadCallbackParameter`] if the input isn't 32-bit float audio.
+    pub fn input(&self) -> Result<&[f32], Error> {
+        let snd = &self.in_data().src_snd;
+        let len = Self::float_samples(snd)?;
+        Ok(unsafe { std::slice::from_raw_parts(snd.dataP as *const f32, len) })
+    }
+
+    /// Interleaved output samples.
+    ///
+    /// Returns [`Error::BadCallbackParameter`] if the output isn't 32-bit float audio.
+    pub fn output(&mut self) -> Result<&mut [f32], Error> {
+        let snd = &self.out_data().dest_snd;
+        let len = Self::float_samples(snd)?;
+        let data = snd.dataP as *mut f32;
+        Ok(unsafe { std::slice::from_raw_parts_mut(data, len) })
+    }
+
+    /// Interleaved input and output samples at once.
+    pub fn buffers(&mut self) -> Result<(&[f32], &mut [f32]), Error> {
+        let src = &self.in_data().src_snd;
+        let dst = &self.out_data().dest_snd;
+        let (src_len, dst_len) = (Self::float_samples(src)?, Self::float_samples(dst)?);
+        unsafe {
+            Ok((
+                std::slice::from_raw_parts(src.dataP as *const f32, src_len),
+                std::slice::from_raw_parts_mut(dst.dataP as *mut f32, dst_len),
+            ))
+        }
+    }
+
+    fn float_samples(snd: &ae_sys::PF_SoundWorld) -> Result<usize, Error> {
+        if snd.dataP.is_null()
+            || snd.fi.format != ae_sys::PF_SIGNED_FLOAT as ae_sys::PF_SoundFormat
+            || snd.fi.sample_size != ae_sys::PF_SSS_4 as ae_sys::PF_SoundSampleSize
+        {
+            return Err(Error::BadCallbackParameter);
+        }
+        Ok(snd.num_samples.max(0) as usize * snd.fi.num_channels.max(0) as usize)
+    }
+}
@@ -0,0 +1,595 @@
+use super::*;
+
+/// A cubic Bézier segment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CubicBezier {
+    pub p0: FloatPoint,
+    pub p1: FloatPoint,
+    pub p2: FloatPoint,
+    pub p3: FloatPoint,
+}
+
+fn point(x: f64, y: f64) -> FloatPoint {
+    FloatPoint { x, y }
+}
+
+impl CubicBezier {
+    /// Segment between two vertices of a mask or a path. The tangents of [`ae_sys::PF_PathVertex`] are relative to the vertex.
+    pub fn from_vertices(from: &ae_sys::PF_PathVertex, to: &ae_sys::PF_PathVertex) -> Self {
+        Self {
+            p0: point(from.x, from.y),
+            p1: point(from.x + from.tan_out_x, from.y + from.tan_out_y),
+            p2: point(to.x + to.tan_in_x, to.y + to.tan_in_y),
+            p3: point(to.x, to.y),
+        }
+    }
+
+    /// Point at `t` in `0.0..=1.0`.
+    pub fn eval(&self, t: f64) -> FloatPoint {
+        let mt = 1.0 - t;
+        let (a, b, c, d) = (mt * mt * mt, 3.0 * mt * mt * t, 3.0 * mt * t * t, t * t * t);
+        point(
+            a * self.p0.x + b * self.p1.x + c * self.p2.x + d * self.p3.x,
+            a * self.p0.y + b * self.p1.y + c * self.p2.y + d * self.p3.y,
+        )
+    }
+
+    /// Append points along the segment to `out`, except `p0`, so that no point of the curve is further than `tolerance` from the polyline.
+    pub fn flatten_into(&self, tolerance: f64, out: &mut Vec<FloatPoint>) {
+        if self.is_line(tolerance) {
+            out.push(self.p3);
+            return;
+        }
+        // The error of a line between samples `h` apart is at most `max |B''| * h² / 8`
+        let dd = |a: FloatPoint, b: FloatPoint, c: FloatPoint| (a.x - 2.0 * b.x + c.x).hypot(a.y - 2.0 * b.y + c.y);
+        let max_dd = 6.0 * dd(self.p0, self.p1, self.p2).max(dd(self.p1, self.p2, self.p3));
+        let count = (max_dd / (8.0 * tolerance.max(1e-6))).sqrt().ceil().clamp(1.0, 1000.0) as usize;
+        out.extend((1..count).map(|i| self.eval(i as f64 / count as f64)));
+        out.push(self.p3);
+    }
+
+    /// Whether the control points are within `tolerance` of the line from `p0` to `p3`, between its ends, like mask segments without tangents.
+    pub fn is_line(&self, tolerance: f64) -> bool {
+        let (dx, dy) = (self.p3.x - self.p0.x, self.p3.y - self.p0.y);
+        let length2 = dx * dx + dy * dy;
+        [self.p1, self.p2].iter().all(|p| {
+            let (px, py) = (p.x - self.p0.x, p.y - self.p0.y);
+            if length2 == 0.0 {
+                return px.hypot(py) <= tolerance;
+            }
+            let t = (px * dx + py * dy) / length2;
+            (0.0..=1.0).contains(&t) && (px * dy - py * dx).abs() / length2.sqrt() <= tolerance
+        })
+    }
+}
+
+/// A path of cubic Bézier segments, like a mask or a shape path.
+///
+/// Convert from After Effects with [`from_path_outline()`](Self::from_path_outline) or [`from_mask_outline()`](Self::from_mask_outline),
+/// and with the `kurbo` feature, to and from [`kurbo::BezPath`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BezierPath {
+    pub segments: Vec<CubicBezier>,
+    /// Whether the path should be closed from the end of the last segment to the start of the first one. Masks are filled as if they're closed.
+    pub closed: bool,
+}
+
+impl BezierPath {
+    /// Path through the vertices of a mask or a path, with tangents relative to each vertex.
+    ///
+    /// If `closed` is true and the last vertex isn't equal to the first one, a segment from the last vertex to the first one is added.
+    pub fn from_vertices(vertices: &[ae_sys::PF_PathVertex], closed: bool) -> Self {
+        let mut segments: Vec<CubicBezier> = vertices.windows(2).map(|v| CubicBezier::from_vertices(&v[0], &v[1])).collect();
+        if let (true, Some(first), Some(last)) = (closed, vertices.first(), vertices.last()) {
+            if vertices.len() > 1 && (first.x, first.y) != (last.x, last.y) {
+                segments.push(CubicBezier::from_vertices(last, first));
+            }
+        }
+        Self { segments, closed }
+    }
+
+    /// Read the segments of an effect path, in layer coordinates.
+    pub fn from_path_outline(path: &PathOutline) -> Result<Self, Error> {
+        // Vertices go from 0 to num_segments; for closed paths, the last one is the first one again
+        let vertices = (0..=path.num_segments()?).map(|i| path.vertex(i)).collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::from_vertices(&vertices, !path.is_open()?))
+    }
+
+    /// Read the segments of a mask outline, in layer coordinates.
+    pub fn from_mask_outline(outline: &aegp::MaskOutline) -> Result<Self, Error> {
+        let vertices = (0..=outline.num_segments()?).map(|i| outline.vertex_info(i)).collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::from_vertices(&vertices, !outline.is_open()?))
+    }
+
+    /// Flatten to a polyline, with no point of the curve further than `tolerance` from it.
+    ///
+    /// Closed paths aren't closed explicitly, the last point connects back to the first one.
+    pub fn flatten(&self, tolerance: f64) -> Vec<FloatPoint> {
+        let mut points = Vec::new();
+        if let Some(first) = self.segments.first() {
+            points.push(first.p0);
+        }
+        for segment in &self.segments {
+            segment.flatten_into(tolerance, &mut points);
+        }
+        if self.closed && points.len() > 1 && points.first() == points.last() {
+            points.pop();
+        }
+        points
+    }
+
+    /// Anti-aliased coverage of the area inside the path, in `0.0..=1.0` for each pixel of a `width` x `height` image, row by row.
+    ///
+    /// Pixel `(x, y)` covers the area from `(x, y)` to `(x + 1, y + 1)`. The path is filled with the non-zero rule, and always as if it's closed.
+    pub fn rasterize(&self, width: usize, height: usize, tolerance: f64) -> Vec<f32> {
+        let stride = width + 2;
+        let mut acc = vec![0.0f32; stride * height];
+        let points = self.flatten(tolerance);
+        for (i, &p0) in points.iter().enumerate() {
+            let p1 = points[(i + 1) % points.len()];
+            for_each_clipped_line(p0, p1, width as f64, |p0, p1| accumulate_line(&mut acc, stride, height, p0, p1));
+        }
+        let mut coverage = Vec::with_capacity(width * height);
+        for row in acc.chunks_exact(stride) {
+            let mut sum = 0.0;
+            coverage.extend(row[..width].iter().map(|x| {
+                sum += x;
+                sum.abs().min(1.0)
+            }));
+        }
+        coverage
+    }
+
+    /// SVG path data, the `d` attribute of a `<path>`, with absolute `M`, `C` and `Z` commands.
+    pub fn to_svg_path_data(&self) -> String {
+        use std::fmt::Write;
+        let mut d = String::new();
+        if let Some(first) = self.segments.first() {
+            let _ = write!(d, "M{} {}", first.p0.x, first.p0.y);
+        }
+        for s in &self.segments {
+            let _ = write!(d, " C{} {} {} {} {} {}", s.p1.x, s.p1.y, s.p2.x, s.p2.y, s.p3.x, s.p3.y);
+        }
+        if self.closed && !self.segments.is_empty() {
+            d.push_str(" Z");
+        }
+        d
+    }
+
+    /// Parse SVG path data, the `d` attribute of a `<path>`, to one path for each subpath.
+    ///
+    /// Lines become segments without tangents and quadratic curves are converted to cubic ones.
+    /// Returns [`Error::InvalidParms`] for malformed data and for arcs, which aren't supported.
+    pub fn from_svg_path_data(d: &str) -> Result<Vec<Self>, Error> {
+        let mut tokens = SvgTokens { s: d.as_bytes(), pos: 0 };
+        let mut paths = Vec::new();
+        let mut path = BezierPath::default();
+        let (mut pos, mut start) = (point(0.0, 0.0), point(0.0, 0.0));
+        // Control point of the previous C/S or Q/T command, for the smooth variants
+        let (mut last_cubic, mut last_quad): (Option<FloatPoint>, Option<FloatPoint>) = (None, None);
+        let mut command = None;
+        loop {
+            let c = match tokens.command() {
+                Some(c) => c,
+                None if tokens.at_end() => break,
+                None => command.ok_or(Error::InvalidParms)?,
+            };
+            let base = if c.is_ascii_lowercase() { pos } else { point(0.0, 0.0) };
+            let next_point = |tokens: &mut SvgTokens| -> Result<FloatPoint, Error> {
+                Ok(point(base.x + tokens.number()?, base.y + tokens.number()?))
+            };
+            let (mut cubic, mut quad) = (None, None);
+            command = Some(c);
+            match c.to_ascii_uppercase() {
+                b'M' => {
+                    if !path.segments.is_empty() {
+                        paths.push(std::mem::take(&mut path));
+                    }
+                    pos = next_point(&mut tokens)?;
+                    start = pos;
+                    // Coordinates after a move are line commands
+                    command = Some(if c == b'm' { b'l' } else { b'L' });
+                }
+                b'L' | b'H' | b'V' => {
+                    let to = match c.to_ascii_uppercase() {
+                        b'H' => point(base.x + tokens.number()?, pos.y),
+                        b'V' => point(pos.x, base.y + tokens.number()?),
+                        _ => next_point(&mut tokens)?,
+                    };
+                    path.segments.push(CubicBezier { p0: pos, p1: pos, p2: to, p3: to });
+                    pos = to;
+                }
+                b'C' | b'S' => {
+                    let p1 = match (c.to_ascii_uppercase(), last_cubic) {
+                        (b'S', Some(last)) => point(2.0 * pos.x - last.x, 2.0 * pos.y - last.y),
+                        (b'S', None) => pos,
+                        _ => next_point(&mut tokens)?,
+                    };
+                    let p2 = next_point(&mut tokens)?;
+                    let p3 = next_point(&mut tokens)?;
+                    path.segments.push(CubicBezier { p0: pos, p1, p2, p3 });
+                    cubic = Some(p2);
+                    pos = p3;
+                }
+                b'Q' | b'T' => {
+                    let q = match (c.to_ascii_uppercase(), last_quad) {
+                        (b'T', Some(last)) => point(2.0 * pos.x - last.x, 2.0 * pos.y - last.y),
+                        (b'T', None) => pos,
+                        _ => next_point(&mut tokens)?,
+                    };
+                    let p3 = next_point(&mut tokens)?;
+                    let p1 = point(pos.x + 2.0 / 3.0 * (q.x - pos.x), pos.y + 2.0 / 3.0 * (q.y - pos.y));
+                    let p2 = point(p3.x + 2.0 / 3.0 * (q.x - p3.x), p3.y + 2.0 / 3.0 * (q.y - p3.y));
+                    path.segments.push(CubicBezier { p0: pos, p1, p2, p3 });
+                    quad = Some(q);
+                    pos = p3;
+                }
+                b'Z' => {
+                    path.closed = true;
+                    if !path.segments.is_empty() {
+                        paths.push(std::mem::take(&mut path));
+                    }
+                    path.closed = false;
+                    pos = start;
+                    command = None;
+                }
+                _ => return Err(Error::InvalidParms),
+            }
+            (last_cubic, last_quad) = (cubic, quad);
+        }
+        if !path.segments.is_empty() {
+            paths.push(path);
+        }
+        Ok(paths)
+    }
+}
+
+struct SvgTokens<'a> {
+    s: &'a [u8],
+    pos: usize,
+}
+
+impl SvgTokens<'_> {
+    fn skip_separators(&mut self) {
+        while self.pos < self.s.len() && (self.s[self.pos].is_ascii_whitespace() || self.s[self.pos] == b',') {
+            self.pos += 1;
+        }
+    }
+    fn at_end(&mut self) -> bool {
+        self.skip_separators();
+        self.pos >= self.s.len()
+    }
+    fn command(&mut self) -> Option<u8> {
+        self.skip_separators();
+        let c = *self.s.get(self.pos)?;
+        // `e` is part of a number, and never a command
+        if c.is_ascii_alphabetic() && c != b'e' && c != b'E' {
+            self.pos += 1;
+            Some(c)
+        } else {
+            None
+        }
+    }
+    fn number(&mut self) -> Result<f64, Error> {
+        self.skip_separators();
+        let start = self.pos;
+        let digits = |this: &mut Self| {
+            let from = this.pos;
+            while this.pos < this.s.len() && this.s[this.pos].is_ascii_digit() {
+                this.pos += 1;
+            }
+            this.pos > from
+        };
+        if matches!(self.s.get(self.pos), Some(b'+' | b'-')) {
+            self.pos += 1;
+        }
+        let mut any = digits(self);
+        if self.s.get(self.pos) == Some(&b'.') {
+            self.pos += 1;
+            any |= digits(self);
+        }
+        if any && matches!(self.s.get(self.pos), Some(b'e' | b'E')) {
+            let mantissa_end = self.pos;
+            self.pos += 1;
+            if matches!(self.s.get(self.pos), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if !digits(self) {
+                self.pos = mantissa_end;
+            }
+        }
+        if !any {
+            return Err(Error::InvalidParms);
+        }
+        std::str::from_utf8(&self.s[start..self.pos]).ok().and_then(|x| x.parse().ok()).ok_or(Error::InvalidParms)
+    }
+}
+
+/// Split the line at `x = 0` and `x = width`, and move the parts outside of the image onto the edges.
+/// Everything left of a pixel covers it in the same way, so this doesn't change the coverage.
+fn for_each_clipped_line(p0: FloatPoint, p1: FloatPoint, width: f64, mut f: impl FnMut(FloatPoint, FloatPoint)) {
+    let mut ts = [0.0, 1.0, 1.0, 1.0];
+    let mut n = 1;
+    for edge in [0.0, width] {
+        let t = (edge - p0.x) / (p1.x - p0.x);
+        if t > 0.0 && t < 1.0 {
+            ts[n] = t;
+            n += 1;
+        }
+    }
+    ts[..=n].sort_by(f64::total_cmp);
+    let at = |t: f64| point((p0.x + (p1.x - p0.x) * t).clamp(0.0, width), p0.y + (p1.y - p0.y) * t);
+    for i in 0..n {
+        f(at(ts[i]), at(ts[i + 1]));
+    }
+}
+
+/// Add the signed area of the line to the accumulation buffer, so that the running sum of a row is the coverage.
+fn accumulate_line(acc: &mut [f32], stride: usize, height: usize, p0: FloatPoint, p1: FloatPoint) {
+    if p0.y == p1.y {
+        return;
+    }
+    let (dir, p0, p1) = if p0.y < p1.y { (1.0, p0, p1) } else { (-1.0, p1, p0) };
+    let dxdy = (p1.x - p0.x) / (p1.y - p0.y);
+    let mut x = p0.x + (0.0 - p0.y).max(0.0) * dxdy;
+    let y_start = p0.y.max(0.0) as usize;
+    let y_end = (p1.y.ceil().max(0.0) as usize).min(height);
+    for y in y_start..y_end {
+        let row = &mut acc[y * stride..(y + 1) * stride];
+        let dy = ((y + 1) as f64).min(p1.y) - (y as f64).max(p0.y);
+        let x_next = x + dxdy * dy;
+        let d = (dy * dir) as f32;
+        let (x0, x1) = if x < x_next { (x, x_next) } else { (x_next, x) };
+        let x0_floor = x0.floor();
+        let x0i = x0_floor as usize;
+        let x1_ceil = x1.ceil();
+        let x1i = x1_ceil as usize;
+        if x1i <= x0i + 1 {
+            let xmf = (0.5 * (x + x_next) - x0_floor) as f32;
+            row[x0i] += d - d * xmf;
+            row[x0i + 1] += d * xmf;
+        } else {
+            let s = (x1 - x0).recip() as f32;
+            let x0f = (x0 - x0_floor) as f32;
+            let a0 = 0.5 * s * (1.0 - x0f) * (1.0 - x0f);
+            let x1f = (x1 - x1_ceil + 1.0) as f32;
+            let am = 0.5 * s * x1f * x1f;
+            row[x0i] += d * a0;
+            if x1i == x0i + 2 {
+                row[x0i + 1] += d * (1.0 - a0 - am);
+            } else {
+                let a1 = s * (1.5 - x0f);
+                row[x0i + 1] += d * (a1 - a0);
+                for value in &mut row[x0i + 2..x1i - 1] {
+                    *value += d * s;
+                }
+                let a2 = a1 + (x1i - x0i - 3) as f32 * s;
+                row[x1i - 1] += d * (1.0 - a2 - am);
+            }
+            row[x1i] += d * am;
+        }
+        x = x_next;
+    }
+}
+
+/// How [`MaskRaster::apply()`] combines a mask with the alpha channel of a layer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MaskRaster {
+    pub mode: MaskMode,
+    pub inverted: bool,
+    /// Blur radius of the mask edge, in pixels.
+    pub feather: f32,
+    /// Opacity of the mask, in `0.0..=1.0`.
+    pub opacity: f32,
+    /// Maximum distance of the flattened polyline from the curves, in pixels.
+    pub tolerance: f64,
+}
+
+impl Default for MaskRaster {
+    fn default() -> Self {
+        Self { mode: MaskMode::Add, inverted: false, feather: 0.0, opacity: 1.0, tolerance: 0.25 }
+    }
+}
+
+impl MaskRaster {
+    /// Mode and inversion of an effect path.
+    pub fn from_path_outline(path: &PathOutline) -> Result<Self, Error> {
+        Ok(Self { mode: path.mask_mode()?, inverted: path.is_inverted()?, ..Default::default() })
+    }
+
+    /// Mode and inversion of a layer mask.
+    pub fn from_mask(mask: &aegp::Mask) -> Result<Self, Error> {
+        let mode = ae_sys::PF_MaskMode::from(mask.mode()?);
+        Ok(Self { mode: mode.into(), inverted: mask.is_inverted()?, ..Default::default() })
+    }
+
+    /// Coverage of `path` for a `width` x `height` image, feathered, inverted and multiplied by the opacity.
+    pub fn coverage(&self, path: &BezierPath, width: usize, height: usize) -> Vec<f32> {
+        let mut coverage = path.rasterize(width, height, self.tolerance);
+        if self.feather > 0.0 {
+            feather(&mut coverage, width, height, self.feather);
+        }
+        for value in &mut coverage {
+            if self.inverted {
+                *value = 1.0 - *value;
+            }
+            *value *= self.opacity;
+        }
+        coverage
+    }
+
+    /// Rasterize `path` and combine it with the alpha channel of `dst` according to [`mode`](Self::mode). The colors aren't changed.
+    ///
+    /// Like in After Effects, masks are applied in order, and the first mask usually starts from a transparent layer,
+    /// or from an opaque layer if it's [`MaskMode::Subtract`] or [`MaskMode::Intersect`].
+    pub fn apply<P: PixelType>(&self, path: &BezierPath, dst: &mut LayerViewMut<P>) {
+        let width = dst.width();
+        let coverage = self.coverage(path, width, dst.height());
+        for (row, coverage) in dst.rows_mut().zip(coverage.chunks_exact(width.max(1))) {
+            for (px, &mask) in row.iter_mut().zip(coverage) {
+                let [alpha, red, green, blue] = px.channels();
+                let alpha = combine_mask(self.mode, P::channel_to_f32(alpha), mask);
+                *px = P::from_channels([P::channel_from_f32(alpha), red, green, blue]);
+            }
+        }
+    }
+}
+
+fn combine_mask(mode: MaskMode, alpha: f32, mask: f32) -> f32 {
+    match mode {
+        MaskMode::None       => alpha,
+        MaskMode::Add        |
+        MaskMode::Accum      => alpha + mask - alpha * mask,
+        MaskMode::Subtract   => alpha * (1.0 - mask),
+        MaskMode::Intersect  => alpha * mask,
+        MaskMode::Lighten    => alpha.max(mask),
+        MaskMode::Darken     => alpha.min(mask),
+        MaskMode::Difference => alpha + mask - 2.0 * alpha * mask,
+    }
+}
+
+/// Separable gaussian blur of a coverage buffer, repeating the edges.
+fn feather(coverage: &mut [f32], width: usize, height: usize, radius: f32) {
+    let kernel = ops::gaussian_kernel(radius);
+    let half = (kernel.len() / 2) as isize;
+    let mut tmp = vec![0.0f32; coverage.len()];
+    let blur = |src: &[f32], dst: &mut [f32], len: usize, lines: usize, step: usize, line_step: usize| {
+        for line in 0..lines {
+            for i in 0..len {
+                dst[line * line_step + i * step] = kernel.iter().enumerate().map(|(k, w)| {
+                    let j = (i as isize + k as isize - half).clamp(0, len as isize - 1) as usize;
+                    w * src[line * line_step + j * step]
+                }).sum();
+            }
+        }
+    };
+    blur(coverage, &mut tmp, width, height, 1, width);
+    blur(&tmp, coverage, height, width, width, 1);
+}
+
+#[cfg(feature = "kurbo")]
+impl From<FloatPoint> for kurbo::Point {
+    fn from(p: FloatPoint) -> Self {
+        kurbo::Point::new(p.x, p.y)
+    }
+}
+
+#[cfg(feature = "kurbo")]
+impl From<kurbo::Point> for FloatPoint {
+    fn from(p: kurbo::Point) -> Self {
+        point(p.x, p.y)
+    }
+}
+
+#[cfg(feature = "kurbo")]
+impl From<CubicBezier> for kurbo::CubicBez {
+    fn from(c: CubicBezier) -> Self {
+        kurbo::CubicBez::new(c.p0, c.p1, c.p2, c.p3)
+    }
+}
+
+#[cfg(feature = "kurbo")]
+impl From<kurbo::CubicBez> for CubicBezier {
+    fn from(c: kurbo::CubicBez) -> Self {
+        Self { p0: c.p0.into(), p1: c.p1.into(), p2: c.p2.into(), p3: c.p3.into() }
+    }
+}
+
+#[cfg(feature = "kurbo")]
+impl From<&BezierPath> for kurbo::BezPath {
+    fn from(path: &BezierPath) -> Self {
+        let mut bez = kurbo::BezPath::new();
+        for (i, segment) in path.segments.iter().enumerate() {
+            if i == 0 {
+                bez.move_to(segment.p0);
+            }
+            bez.curve_to(segment.p1, segment.p2, segment.p3);
+        }
+        if path.closed && !path.segments.is_empty() {
+            bez.close_path();
+        }
+        bez
+    }
+}
+
+#[cfg(feature = "kurbo")]
+impl From<&kurbo::BezPath> for BezierPath {
+    /// Lines and quadratic curves are converted to cubic ones. Only the first subpath is used.
+    fn from(bez: &kurbo::BezPath) -> Self {
+        let mut path = BezierPath::default();
+        let mut started = false;
+        for element in bez.elements() {
+            match element {
+                kurbo::PathEl::MoveTo(_) if started => break,
+                kurbo::PathEl::MoveTo(_) => started = true,
+                kurbo::PathEl::ClosePath => {
+                    path.closed = true;
+                    break;
+                }
+                _ => {}
+            }
+        }
+        for segment in bez.segments() {
+            let cubic = match segment {
+                kurbo::PathSeg::Line(line) => kurbo::CubicBez::new(line.p0, line.p0.lerp(line.p1, 1.0 / 3.0), line.p0.lerp(line.p1, 2.0 / 3.0), line.p1),
+                kurbo::PathSeg::Quad(quad) => quad.raise(),
+                kurbo::PathSeg::Cubic(cubic) => cubic,
+            };
+            if path.segments.last().is_some_and(|last| FloatPoint::from(cubic.p0) != last.p3) {
+                break;
+            }
+            path.segments.push(cubic.into());
+        }
+        path
+    }
+}
+
+#[test]
+fn test_bezier_path() {
+    let vertex = |x, y| ae_sys::PF_PathVertex { x, y, tan_in_x: 0.0, tan_in_y: 0.0, tan_out_x: 0.0, tan_out_y: 0.0 };
+    let square = BezierPath::from_vertices(&[vertex(2.0, 2.0), vertex(6.0, 2.0), vertex(6.0, 6.0), vertex(2.0, 6.0)], true);
+    assert_eq!(square.segments.len(), 4);
+    assert_eq!(square.flatten(0.25).len(), 4);
+
+    let coverage = square.rasterize(8, 8, 0.25);
+    assert_eq!(coverage[3 * 8 + 3], 1.0);
+    assert_eq!(coverage[8 + 3], 0.0);
+    assert_eq!(coverage.iter().sum::<f32>(), 16.0);
+
+    // Half pixel edges, and parts outside of the image
+    let offset = BezierPath::from_vertices(&[vertex(-4.0, 0.5), vertex(2.5, 0.5), vertex(2.5, 1.5), vertex(-4.0, 1.5)], true);
+    let coverage = offset.rasterize(4, 2, 0.25);
+    assert_eq!(coverage, [0.5, 0.5, 0.25, 0.0, 0.5, 0.5, 0.25, 0.0]);
+
+    let circle = BezierPath::from_vertices(&[
+        ae_sys::PF_PathVertex { x: 10.0, y: 0.0, tan_in_x: 0.0, tan_in_y: -5.52, tan_out_x: 0.0, tan_out_y: 5.52 },
+        ae_sys::PF_PathVertex { x: 0.0, y: 10.0, tan_in_x: 5.52, tan_in_y: 0.0, tan_out_x: -5.52, tan_out_y: 0.0 },
+        ae_sys::PF_PathVertex { x: -10.0, y: 0.0, tan_in_x: 0.0, tan_in_y: 5.52, tan_out_x: 0.0, tan_out_y: -5.52 },
+        ae_sys::PF_PathVertex { x: 0.0, y: -10.0, tan_in_x: -5.52, tan_in_y: 0.0, tan_out_x: 5.52, tan_out_y: 0.0 },
+    ], true);
+    for p in circle.flatten(0.1) {
+        assert!((p.x.hypot(p.y) - 10.0).abs() < 0.1);
+    }
+
+    let raster = MaskRaster { mode: MaskMode::Subtract, inverted: true, ..Default::default() };
+    assert_eq!(raster.coverage(&square, 8, 8)[0], 1.0);
+    assert_eq!(combine_mask(raster.mode, 1.0, 1.0), 0.0);
+}
+
+#[test]
+fn test_svg_path_data() {
+    let paths = BezierPath::from_svg_path_data("M10,10 h20 v20 H10 z m5 5 c1 0 2 1 2 2 s1 2 2 2 q1-1 2 0 t2 0 L1e1.5").unwrap();
+    assert_eq!(paths.len(), 2);
+    assert!(paths[0].closed && !paths[1].closed);
+    assert_eq!(paths[0].segments.len(), 3);
+    assert_eq!(paths[0].segments[1].p3, point(30.0, 30.0));
+    let s = paths[1].segments[1];
+    assert_eq!((s.p0, s.p1, s.p3), (point(17.0, 17.0), point(17.0, 18.0), point(19.0, 19.0)));
+    assert_eq!(paths[1].segments[3].p3, point(23.0, 19.0));
+    assert_eq!(paths[1].segments[4].p3, point(10.0, 0.5));
+
+    let round_trip = BezierPath::from_svg_path_data(&paths[1].to_svg_path_data()).unwrap();
+    assert_eq!(round_trip, [paths[1].clone()]);
+    assert!(BezierPath::from_svg_path_data("M0 0 A1 1 0 0 1 2 2").is_err());
+    assert!(BezierPath::from_svg_path_data("M0 0 L1").is_err());
+}
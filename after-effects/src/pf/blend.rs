@@ -0,0 +1,242 @@
+//! Transfer modes (blend modes) as pure functions, following After Effects semantics.
+//!
+//! Every [`TransferMode`] is implemented for all [`PixelType`]s. Pixels are straight (not premultiplied) alpha,
+//! like in After Effects layers. Use [`blend_premultiplied()`] for premultiplied data.
+//!
+//! The blended color is composited with the W3C compositing formula:
+//! `color = src * src_alpha * (1 - dst_alpha) + dst * dst_alpha * (1 - src_alpha) + B(dst, src) * src_alpha * dst_alpha`,
+//! where `B` is the blend function of the mode. Results aren't clamped for 32 bpc, so additive modes keep values above 1.0.
+//!
+//! [`aegp::LayerTransferMode`](crate::aegp::LayerTransferMode) holds the same [`TransferMode`], so layer modes can be reproduced directly.
+
+use super::*;
+
+/// Blend `src` onto `dst` with the given mode. `opacity` (`0.0..=1.0`) scales the alpha of `src`.
+///
+/// [`TransferMode::Dissolve`] needs a random value per pixel, which isn't available here, so it uses a fixed threshold of 0.5.
+/// Use [`dissolve()`] with [`dissolve_noise()`] for the real thing.
+pub fn blend<P: PixelType>(mode: TransferMode, src: P, dst: P, opacity: f32) -> P {
+    P::from_f32(blend_f32(mode, src.to_f32(), dst.to_f32(), opacity))
+}
+
+/// Blend a row of `src` pixels onto `dst`, in place. Extra pixels of the longer row are ignored.
+pub fn blend_row<P: PixelType>(mode: TransferMode, src: &[P], dst: &mut [P], opacity: f32) {
+    for (s, d) in src.iter().zip(dst) {
+        *d = blend(mode, *s, *d, opacity);
+    }
+}
+
+/// Version of [`blend()`] for premultiplied pixels.
+pub fn blend_premultiplied(mode: TransferMode, src: PixelF32, dst: PixelF32, opacity: f32) -> PixelF32 {
+    blend_f32(mode, src.unpremultiply(), dst.unpremultiply(), opacity).premultiply()
+}
+
+/// Dissolve: `src` replaces `dst` where `noise` (`0.0..1.0`) is below its alpha times `opacity`.
+pub fn dissolve<P: PixelType>(src: P, dst: P, opacity: f32, noise: f32) -> P {
+    let s = src.to_f32();
+    if noise < s.alpha * opacity {
+        P::from_f32(over(PixelF32 { alpha: 1.0, ..s }, dst.to_f32()))
+    } else {
+        dst
+    }
+}
+
+/// Deterministic per-pixel noise in `0.0..1.0` for [`dissolve()`], like the random seed of [`CompositeMode`].
+pub fn dissolve_noise(x: i32, y: i32, seed: i32) -> f32 {
+    let mut h = (x as u32).wrapping_mul(0x8da6b343) ^ (y as u32).wrapping_mul(0xd8163841) ^ (seed as u32).wrapping_mul(0xcb1ab31f);
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x7feb352d);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x846ca68b);
+    h ^= h >> 16;
+    (h >> 8) as f32 / (1u32 << 24) as f32
+}
+
+/// Blend straight alpha float pixels.
+pub fn blend_f32(mode: TransferMode, src: PixelF32, dst: PixelF32, opacity: f32) -> PixelF32 {
+    let opacity = opacity.clamp(0.0, 1.0);
+    let s = PixelF32 { alpha: src.alpha * opacity, ..src };
+    let d = dst;
+    match mode {
+        TransferMode::Copy => s,
+        TransferMode::Behind => over(d, s),
+        TransferMode::Dissolve => dissolve(src, dst, opacity, 0.5),
+
+        // Track mattes: src is the matte, dst keeps its color
+        TransferMode::MultiplyAlpha        => matte(d, s.alpha),
+        TransferMode::MultiplyNotAlpha     => matte(d, 1.0 - s.alpha),
+        TransferMode::MultiplyAlphaLuma    => matte(d, luminosity(&s) * s.alpha),
+        TransferMode::MultiplyNotAlphaLuma => matte(d, 1.0 - luminosity(&s) * s.alpha),
+
+        TransferMode::AddiditivePremul => {
+            // Colors are added premultiplied, like AE's "Luminescent Premul"
+            let alpha = s.alpha + d.alpha * (1.0 - s.alpha);
+            let premultiplied = |s_c: f32, d_c: f32| s_c * s.alpha + d_c * d.alpha;
+            unpremultiplied(alpha, premultiplied(s.red, d.red), premultiplied(s.green, d.green), premultiplied(s.blue, d.blue))
+        }
+        TransferMode::AlphaAdd => {
+            let mut result = over(s, d);
+            result.alpha = (s.alpha + d.alpha).min(1.0);
+            result
+        }
+
+        TransferMode::Hue | TransferMode::Saturation | TransferMode::Color | TransferMode::Luminosity |
+        TransferMode::LighterColor | TransferMode::DarkerColor => {
+            let (r, g, b) = blend_non_separable(mode, (s.red, s.green, s.blue), (d.red, d.green, d.blue));
+            composite(s, d, r, g, b)
+        }
+
+        _ => composite(s, d, blend_channel(mode, s.red, d.red), blend_channel(mode, s.green, d.green), blend_channel(mode, s.blue, d.blue)),
+    }
+}
+
+/// Blend function of a separable mode for one color channel, `B(dst, src)` in the W3C terminology.
+///
+/// Modes which aren't separable or don't blend colors (like [`TransferMode::Hue`] or the track matte modes) return `src`.
+pub fn blend_channel(mode: TransferMode, src: f32, dst: f32) -> f32 {
+    let (s, d) = (src, dst);
+    match mode {
+        TransferMode::Add | TransferMode::LinearDodge => s + d,
+        TransferMode::Mulitply    => s * d,
+        TransferMode::Screen      => s + d - s * d,
+        TransferMode::Overlay     => hard_light(d, s),
+        TransferMode::SoftLight   => soft_light(s, d),
+        TransferMode::HardLight   => hard_light(s, d),
+        TransferMode::Darken      => s.min(d),
+        TransferMode::Lighten     => s.max(d),
+        TransferMode::Difference | TransferMode::Difference2 => (s - d).abs(),
+        TransferMode::ColorDodge  => if s >= 1.0 { 1.0 } else { (d / (1.0 - s)).min(1.0) },
+        TransferMode::ColorDodge2 => if d <= 0.0 { 0.0 } else if s >= 1.0 { 1.0 } else { (d / (1.0 - s)).min(1.0) },
+        TransferMode::ColorBurn   => if s <= 0.0 { 0.0 } else { (1.0 - (1.0 - d) / s).max(0.0) },
+        TransferMode::ColorBurn2  => if d >= 1.0 { 1.0 } else if s <= 0.0 { 0.0 } else { 1.0 - ((1.0 - d) / s).min(1.0) },
+        TransferMode::Exclusion   => s + d - 2.0 * s * d,
+        TransferMode::LinearBurn  => s + d - 1.0,
+        TransferMode::LinearLight => d + 2.0 * s - 1.0,
+        TransferMode::VividLight  => {
+            if s <= 0.5 {
+                blend_channel(TransferMode::ColorBurn2, 2.0 * s, d)
+            } else {
+                blend_channel(TransferMode::ColorDodge2, 2.0 * s - 1.0, d)
+            }
+        }
+        TransferMode::PinLight    => if s <= 0.5 { d.min(2.0 * s) } else { d.max(2.0 * s - 1.0) },
+        TransferMode::HardMix     => if s + d >= 1.0 { 1.0 } else { 0.0 },
+        TransferMode::Subtract    => d - s,
+        TransferMode::Divide      => if s <= 0.0 { if d > 0.0 { 1.0 } else { 0.0 } } else { d / s },
+        _ => s,
+    }
+}
+
+fn hard_light(s: f32, d: f32) -> f32 {
+    if s <= 0.5 {
+        d * 2.0 * s
+    } else {
+        let s = 2.0 * s - 1.0;
+        s + d - s * d
+    }
+}
+
+fn soft_light(s: f32, d: f32) -> f32 {
+    if s <= 0.5 {
+        d - (1.0 - 2.0 * s) * d * (1.0 - d)
+    } else {
+        let dd = if d <= 0.25 { ((16.0 * d - 12.0) * d + 4.0) * d } else { d.max(0.0).sqrt() };
+        d + (2.0 * s - 1.0) * (dd - d)
+    }
+}
+
+type Rgb = (f32, f32, f32);
+
+fn lum((r, g, b): Rgb) -> f32 {
+    0.3 * r + 0.59 * g + 0.11 * b
+}
+fn luminosity(p: &PixelF32) -> f32 {
+    lum((p.red, p.green, p.blue))
+}
+fn clip_color(c: Rgb) -> Rgb {
+    let l = lum(c);
+    let (n, x) = (c.0.min(c.1).min(c.2), c.0.max(c.1).max(c.2));
+    let mut c = [c.0, c.1, c.2];
+    if n < 0.0 {
+        c.iter_mut().for_each(|v| *v = l + (*v - l) * l / (l - n));
+    }
+    if x > 1.0 {
+        c.iter_mut().for_each(|v| *v = l + (*v - l) * (1.0 - l) / (x - l));
+    }
+    (c[0], c[1], c[2])
+}
+fn set_lum(c: Rgb, l: f32) -> Rgb {
+    let d = l - lum(c);
+    clip_color((c.0 + d, c.1 + d, c.2 + d))
+}
+fn sat((r, g, b): Rgb) -> f32 {
+    r.max(g).max(b) - r.min(g).min(b)
+}
+fn set_sat(c: Rgb, s: f32) -> Rgb {
+    let mut v = [c.0, c.1, c.2];
+    let (max, min) = (v.iter().cloned().fold(f32::MIN, f32::max), v.iter().cloned().fold(f32::MAX, f32::min));
+    if max > min {
+        v.iter_mut().for_each(|x| *x = (*x - min) * s / (max - min));
+    } else {
+        v = [0.0; 3];
+    }
+    (v[0], v[1], v[2])
+}
+
+fn blend_non_separable(mode: TransferMode, s: Rgb, d: Rgb) -> Rgb {
+    match mode {
+        TransferMode::Hue          => set_lum(set_sat(s, sat(d)), lum(d)),
+        TransferMode::Saturation   => set_lum(set_sat(d, sat(s)), lum(d)),
+        TransferMode::Color        => set_lum(s, lum(d)),
+        TransferMode::Luminosity   => set_lum(d, lum(s)),
+        TransferMode::LighterColor => if lum(s) > lum(d) { s } else { d },
+        TransferMode::DarkerColor  => if lum(s) < lum(d) { s } else { d },
+        _ => s,
+    }
+}
+
+/// W3C source-over compositing with the blended color.
+fn composite(s: PixelF32, d: PixelF32, r: f32, g: f32, b: f32) -> PixelF32 {
+    let alpha = s.alpha + d.alpha * (1.0 - s.alpha);
+    let mix = |s_c: f32, d_c: f32, blended: f32| {
+        s_c * s.alpha * (1.0 - d.alpha) + d_c * d.alpha * (1.0 - s.alpha) + blended * s.alpha * d.alpha
+    };
+    unpremultiplied(alpha, mix(s.red, d.red, r), mix(s.green, d.green, g), mix(s.blue, d.blue, b))
+}
+
+/// Porter-Duff `top` over `bottom`.
+fn over(top: PixelF32, bottom: PixelF32) -> PixelF32 {
+    composite(top, bottom, top.red, top.green, top.blue)
+}
+
+fn matte(d: PixelF32, matte: f32) -> PixelF32 {
+    PixelF32 { alpha: d.alpha * matte.clamp(0.0, 1.0), ..d }
+}
+
+fn unpremultiplied(alpha: f32, red: f32, green: f32, blue: f32) -> PixelF32 {
+    if alpha <= 0.0 {
+        return PixelF32 { alpha: 0.0, red: 0.0, green: 0.0, blue: 0.0 };
+    }
+    PixelF32 { alpha, red: red / alpha, green: green / alpha, blue: blue / alpha }
+}
+
+#[test]
+fn test_blend() {
+    let px = |alpha, value| PixelF32 { alpha, red: value, green: value, blue: value };
+    let red = |p: PixelF32| (p.red * 1000.0).round() / 1000.0;
+
+    assert_eq!(red(blend_f32(TransferMode::Screen, px(1.0, 0.5), px(1.0, 0.5), 1.0)), 0.75);
+    assert_eq!(red(blend_f32(TransferMode::Mulitply, px(1.0, 0.5), px(1.0, 0.5), 0.5)), 0.375);
+    assert_eq!(red(blend_f32(TransferMode::Add, px(1.0, 0.8), px(1.0, 0.8), 1.0)), 1.6);
+    assert_eq!(red(blend_f32(TransferMode::Overlay, px(1.0, 0.5), px(1.0, 0.25), 1.0)), 0.25);
+    // Blending onto a transparent pixel shows the source
+    assert_eq!(red(blend_f32(TransferMode::Difference, px(1.0, 0.3), px(0.0, 0.9), 1.0)), 0.3);
+    // Track matte
+    assert_eq!(blend_f32(TransferMode::MultiplyAlpha, px(0.5, 0.0), px(1.0, 0.9), 1.0).alpha, 0.5);
+    // Integer pixels are clamped
+    let p8 = blend(TransferMode::Add, Pixel8 { alpha: 255, red: 200, green: 0, blue: 0 }, Pixel8 { alpha: 255, red: 200, green: 0, blue: 0 }, 1.0);
+    assert_eq!(p8.red, 255);
+    // Color of pure red onto gray keeps the luminosity of gray
+    let color = blend_f32(TransferMode::Color, PixelF32 { alpha: 1.0, red: 1.0, green: 0.0, blue: 0.0 }, px(1.0, 0.5), 1.0);
+    assert!((luminosity(&color) - 0.5).abs() < 1e-5 && color.red > color.green);
+}
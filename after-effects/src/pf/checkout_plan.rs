@@ -0,0 +1,193 @@
+use super::*;
+use std::any::Any;
+
+/// A layer parameter requested in [`Command::SmartPreRender`] by [`CheckoutPlan`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct CheckoutRequest {
+    /// Index of the layer parameter. `0` is the input layer.
+    pub param: i32,
+    /// Offset from the current time, in frames (multiples of [`InData::time_step()`]).
+    pub frame_offset: i32,
+    /// Pixels to add on each side of the output request, in full resolution. Scaled by the downsample factors.
+    pub expand: i32,
+}
+
+/// Declarative version of [`PreRenderCallbacks::checkout_layer()`] for effects which need layers at several times or with extra pixels.
+///
+/// In [`Command::SmartPreRender`], list the inputs and call [`checkout()`](Self::checkout). This assigns the checkout IDs,
+/// unions the result rects into the output and stores the plan as the pre-render data.
+/// In [`Command::SmartRender`], [`CheckedOutLayers::new()`] checks all of them out, and checks them in again when it's dropped.
+///
+/// Requests for the same parameter and frame offset are merged, with the largest expansion.
+///
+/// ```ignore
+/// ae::Command::SmartPreRender { mut extra } => {
+///     CheckoutPlan::new()
+///         .layer(0, 0, 0)
+///         .layer(0, -1, 0)
+///         .layer(0, -2, 0)
+///         .with_data(decay)
+///         .checkout(&mut extra)?;
+/// }
+/// ae::Command::SmartRender { extra } => {
+///     let layers = CheckedOutLayers::<f32>::new(&extra)?;
+///     let previous = layers.layer(0, -1);
+///     let decay = layers.data();
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CheckoutPlan<T = ()> {
+    requests: Vec<CheckoutRequest>,
+    data: T,
+}
+
+impl CheckoutPlan<()> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<T: Any> CheckoutPlan<T> {
+    /// Request the layer parameter `param`, `frame_offset` frames from the current time, with `expand` pixels more on each side than the output.
+    pub fn layer(self, param: i32, frame_offset: i32, expand: i32) -> Self {
+        self.request(CheckoutRequest { param, frame_offset, expand })
+    }
+
+    pub fn request(mut self, request: CheckoutRequest) -> Self {
+        match self.requests.iter_mut().find(|x| x.param == request.param && x.frame_offset == request.frame_offset) {
+            Some(existing) => existing.expand = existing.expand.max(request.expand),
+            None => self.requests.push(request),
+        }
+        self
+    }
+
+    /// Additional pre-render data, available from [`CheckedOutLayers::data()`].
+    pub fn with_data<U: Any>(self, data: U) -> CheckoutPlan<U> {
+        CheckoutPlan { requests: self.requests, data }
+    }
+
+    pub fn requests(&self) -> &[CheckoutRequest] {
+        &self.requests
+    }
+
+    /// Check out all requested layers and union their result rects into the output of `extra`.
+    ///
+    /// The checkout ID of each request is its index in [`requests()`](Self::requests).
+    /// The plan is stored with [`PreRenderExtra::set_pre_render_data()`], so don't set other pre-render data.
+    pub fn checkout(self, extra: &mut PreRenderExtra) -> Result<(), Error> {
+        let in_data = InData::from_raw(extra.in_data_ptr);
+        let callbacks = extra.callbacks();
+        let output_request = extra.output_request();
+        let (downsample_x, downsample_y) = (f64::from(in_data.downsample_x()), f64::from(in_data.downsample_y()));
+
+        for (checkout_id, request) in self.requests.iter().enumerate() {
+            let mut render_request = output_request;
+            render_request.rect = expand_rect(render_request.rect, request.expand, downsample_x, downsample_y);
+
+            let result = callbacks.checkout_layer(
+                request.param,
+                checkout_id as i32,
+                &render_request,
+                in_data.current_time() + request.frame_offset * in_data.time_step(),
+                in_data.time_step(),
+                in_data.time_scale(),
+            )?;
+            extra.union_result_rect(result.result_rect.into());
+            extra.union_max_result_rect(result.max_result_rect.into());
+        }
+        extra.set_pre_render_data(self);
+        Ok(())
+    }
+}
+
+/// Grows `rect` by `expand` full resolution pixels on each side, scaled by the downsample factors and rounded up.
+fn expand_rect(mut rect: ae_sys::PF_LRect, expand: i32, downsample_x: f64, downsample_y: f64) -> ae_sys::PF_LRect {
+    let expand_x = (expand as f64 * downsample_x).ceil() as i32;
+    let expand_y = (expand as f64 * downsample_y).ceil() as i32;
+    rect.left   -= expand_x;
+    rect.top    -= expand_y;
+    rect.right  += expand_x;
+    rect.bottom += expand_y;
+    rect
+}
+
+/// Layers checked out in [`Command::SmartRender`] according to the [`CheckoutPlan`] from [`Command::SmartPreRender`].
+///
+/// All layers are checked in when this is dropped.
+pub struct CheckedOutLayers<'a, T: Any = ()> {
+    callbacks: SmartRenderCallbacks,
+    plan: &'a CheckoutPlan<T>,
+    layers: Vec<Option<Layer>>,
+}
+
+impl<'a, T: Any> CheckedOutLayers<'a, T> {
+    /// Check out the layers of the plan stored in the pre-render data.
+    ///
+    /// Returns [`Error::BadCallbackParameter`] if [`CheckoutPlan::checkout()`] wasn't called in [`Command::SmartPreRender`],
+    /// or if the plan data has a different type than `T`.
+    pub fn new(extra: &'a SmartRenderExtra) -> Result<Self, Error> {
+        let plan = extra.pre_render_data_any()
+            .and_then(|data| data.downcast_ref::<CheckoutPlan<T>>())
+            .ok_or(Error::BadCallbackParameter)?;
+        let mut layers = Self {
+            callbacks: extra.callbacks(),
+            plan,
+            layers: Vec::with_capacity(plan.requests.len()),
+        };
+        for checkout_id in 0..plan.requests.len() {
+            // On error, drop checks in the layers checked out so far
+            let layer = layers.callbacks.checkout_layer_pixels(checkout_id as u32)?;
+            layers.layers.push(layer);
+        }
+        Ok(layers)
+    }
+
+    /// The layer of parameter `param` at `frame_offset` frames from the current time.
+    ///
+    /// Returns `None` if it wasn't requested, or if there are no pixels, for example for an adjustment layer with nothing beneath it.
+    pub fn layer(&self, param: i32, frame_offset: i32) -> Option<&Layer> {
+        let index = self.plan.requests.iter().position(|x| x.param == param && x.frame_offset == frame_offset)?;
+        self.layers[index].as_ref()
+    }
+
+    /// All requests with their layers, in the order they were requested.
+    pub fn iter(&self) -> impl Iterator<Item = (&CheckoutRequest, Option<&Layer>)> {
+        self.plan.requests.iter().zip(self.layers.iter().map(Option::as_ref))
+    }
+
+    /// Data set with [`CheckoutPlan::with_data()`].
+    pub fn data(&self) -> &T {
+        &self.plan.data
+    }
+}
+
+impl<T: Any> Drop for CheckedOutLayers<'_, T> {
+    fn drop(&mut self) {
+        for checkout_id in 0..self.layers.len() {
+            if let Err(e) = self.callbacks.checkin_layer_pixels(checkout_id as u32) {
+                log::error!("Failed to check in layer {checkout_id}: {e:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn test_checkout_plan() {
+    let plan = CheckoutPlan::new().layer(0, 0, 0).layer(1, -1, 4).layer(0, 0, 10).with_data(0.5f32);
+    assert_eq!(plan.requests(), [
+        CheckoutRequest { param: 0, frame_offset: 0, expand: 10 },
+        CheckoutRequest { param: 1, frame_offset: -1, expand: 4 },
+    ]);
+    assert_eq!(plan.data, 0.5);
+
+    let rect = ae_sys::PF_LRect { left: 10, top: 20, right: 30, bottom: 40 };
+    let expanded = |expand, downsample_x, downsample_y| {
+        let x = expand_rect(rect, expand, downsample_x, downsample_y);
+        [x.left, x.top, x.right, x.bottom]
+    };
+    assert_eq!(expanded(3, 1.0, 1.0), [7, 17, 33, 43]);
+    // ceil(3 * 1/2) = 2, ceil(3 * 1/3) = 1
+    assert_eq!(expanded(3, 0.5, 1.0 / 3.0), [8, 19, 32, 41]);
+    assert_eq!(expanded(4, 0.25, 0.25), [9, 19, 31, 41]);
+    assert_eq!(expanded(0, 0.5, 0.5), [10, 20, 30, 40]);
+}
@@ -128,12 +128,13 @@ pub enum Command {
     /// Populate `out_data.dest_snd` with effect-ed audio. All fields in [`InData`] are valid.
     ///
     /// If your response to this selector is interrupted (your calls to PF_ABORT or PF_PROGRESS returns an error code), your results will not be used.
-    AudioRender,
+    AudioRender { extra: AudioRenderExtra },
     ///
     /// Sent before every audio render. Request a time span of input audio. Allocate and initialize any sequence-specific data.
     ///
-    /// If your effect requires input from a time span other than the output time span, update the startsampL and endsampL field in [`OutData`].
-    AudioSetup,
+    /// If your effect requires input from a time span other than the output time span, update the startsampL and endsampL field in [`OutData`]
+    /// with [`AudioRenderExtra::set_input_start_sample`] and [`AudioRenderExtra::set_input_duration_samples`].
+    AudioSetup { extra: AudioRenderExtra },
     /// Free memory allocated during [`Command::AudioSetup`].
     AudioSetdown,
     /// Manage your arbitrary data type. You’ll only receive this if you’ve registered a custom data type parameter.
@@ -244,6 +245,7 @@ impl Command {
     pub fn from_entry_point(
         cmd: impl Into<RawCommand>,
         in_data_ptr: *const ae_sys::PF_InData,
+        out_data_ptr: *mut ae_sys::PF_OutData,
         params: *mut *mut ae_sys::PF_ParamDef,
         output: *mut ae_sys::PF_LayerDef,
         extra: *mut std::ffi::c_void,
@@ -281,8 +283,12 @@ impl Command {
             },
             RawCommand::CompletelyGeneral => Command::CompletelyGeneral,
             RawCommand::QueryDynamicFlags => Command::QueryDynamicFlags,
-            RawCommand::AudioRender => Command::AudioRender,
-            RawCommand::AudioSetup => Command::AudioSetup,
+            RawCommand::AudioRender => Command::AudioRender {
+                extra: AudioRenderExtra::from_raw(in_data_ptr, out_data_ptr),
+            },
+            RawCommand::AudioSetup => Command::AudioSetup {
+                extra: AudioRenderExtra::from_raw(in_data_ptr, out_data_ptr),
+            },
             RawCommand::AudioSetdown => Command::AudioSetdown,
             RawCommand::ArbitraryCallback => Command::ArbitraryCallback {
                 extra: ArbParamsExtra::from_raw(extra as *mut ae_sys::PF_ArbParamsExtra),
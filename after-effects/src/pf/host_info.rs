@@ -0,0 +1,288 @@
+use super::*;
+use std::sync::RwLock;
+
+/// The application running the plug-in.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HostProduct {
+    AfterEffects,
+    Premiere,
+    /// Another host, with its application creator code like `b"FXTC"`, or its name if it's only known from the loader strings.
+    Other(String),
+}
+
+impl HostProduct {
+    /// From the application creator code in [`InData::application_id()`].
+    pub fn from_application_id(id: [u8; 4]) -> Self {
+        match &id {
+            b"FXTC" => Self::AfterEffects,
+            b"PrMr" => Self::Premiere,
+            _ => Self::Other(String::from_utf8_lossy(&id).into_owned()),
+        }
+    }
+
+    /// From the host name passed to the plug-in data entry point, like `"AfterEffects"` or `"PremierePro"`.
+    pub fn from_host_name(name: &str) -> Self {
+        let name_lower = name.to_ascii_lowercase();
+        if name_lower.contains("aftereffects") || name_lower.contains("after effects") {
+            Self::AfterEffects
+        } else if name_lower.contains("premiere") {
+            Self::Premiere
+        } else {
+            Self::Other(name.to_owned())
+        }
+    }
+}
+
+/// Version of the host application, like `24.1.0x46`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HostVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub bug: u32,
+    pub build: Option<u32>,
+}
+
+impl HostVersion {
+    pub const fn new(major: u32, minor: u32, bug: u32) -> Self {
+        Self { major, minor, bug, build: None }
+    }
+
+    /// Parse a version like `24.1`, `24.1.0x46` or `24.1.0 (Build 46)`.
+    ///
+    /// The build is the first number after the dotted version. Returns `None` if the string doesn't start with a number.
+    pub fn parse(version: &str) -> Option<Self> {
+        let version = version.trim();
+        let dotted_len = version.find(|c: char| !c.is_ascii_digit() && c != '.').unwrap_or(version.len());
+        let (dotted, rest) = version.split_at(dotted_len);
+        let mut numbers = dotted.split('.').map(|x| x.parse::<u32>().ok());
+        let major = numbers.next().flatten()?;
+        let minor = numbers.next().flatten().unwrap_or(0);
+        let bug = numbers.next().flatten().unwrap_or(0);
+        let build = rest
+            .split(|c: char| !c.is_ascii_digit())
+            .find(|x| !x.is_empty())
+            .and_then(|x| x.parse().ok());
+        Some(Self { major, minor, bug, build })
+    }
+
+    /// Whether this version is `major.minor` or newer.
+    pub fn at_least(&self, major: u32, minor: u32) -> bool {
+        (self.major, self.minor) >= (major, minor)
+    }
+}
+
+impl std::fmt::Display for HostVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.bug)?;
+        if let Some(build) = self.build {
+            write!(f, "x{build}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Effect API versions of After Effects releases, from the `PF_AE*_PLUG_IN_VERSION` constants, newest first.
+///
+/// Releases with the same spec version as an older one, like 15.1, are left out.
+const AE_SPEC_VERSIONS: &[((u32, u32), HostVersion)] = &[
+    ((ae_sys::PF_AE234_PLUG_IN_VERSION,  ae_sys::PF_AE234_PLUG_IN_SUBVERS), HostVersion::new(23, 4, 0)),
+    ((ae_sys::PF_AE220_PLUG_IN_VERSION,  ae_sys::PF_AE220_PLUG_IN_SUBVERS), HostVersion::new(22, 0, 0)),
+    ((ae_sys::PF_AE184_PLUG_IN_VERSION,  ae_sys::PF_AE184_PLUG_IN_SUBVERS), HostVersion::new(18, 4, 0)),
+    ((ae_sys::PF_AE182_PLUG_IN_VERSION,  ae_sys::PF_AE182_PLUG_IN_SUBVERS), HostVersion::new(18, 2, 0)),
+    ((ae_sys::PF_AE180_PLUG_IN_VERSION,  ae_sys::PF_AE180_PLUG_IN_SUBVERS), HostVersion::new(18, 0, 0)),
+    ((ae_sys::PF_AE177_PLUG_IN_VERSION,  ae_sys::PF_AE177_PLUG_IN_SUBVERS), HostVersion::new(17, 7, 0)),
+    ((ae_sys::PF_AE176_PLUG_IN_VERSION,  ae_sys::PF_AE176_PLUG_IN_SUBVERS), HostVersion::new(17, 6, 0)),
+    ((ae_sys::PF_AE175_PLUG_IN_VERSION,  ae_sys::PF_AE175_PLUG_IN_SUBVERS), HostVersion::new(17, 5, 0)),
+    ((ae_sys::PF_AE171_PLUG_IN_VERSION,  ae_sys::PF_AE171_PLUG_IN_SUBVERS), HostVersion::new(17, 1, 0)),
+    ((ae_sys::PF_AE170_PLUG_IN_VERSION,  ae_sys::PF_AE170_PLUG_IN_SUBVERS), HostVersion::new(17, 0, 0)),
+    ((ae_sys::PF_AE161_PLUG_IN_VERSION,  ae_sys::PF_AE161_PLUG_IN_SUBVERS), HostVersion::new(16, 1, 0)),
+    ((ae_sys::PF_AE160_PLUG_IN_VERSION,  ae_sys::PF_AE160_PLUG_IN_SUBVERS), HostVersion::new(16, 0, 0)),
+    ((ae_sys::PF_AE150_PLUG_IN_VERSION,  ae_sys::PF_AE150_PLUG_IN_SUBVERS), HostVersion::new(15, 0, 0)),
+    ((ae_sys::PF_AE142_PLUG_IN_VERSION,  ae_sys::PF_AE142_PLUG_IN_SUBVERS), HostVersion::new(14, 2, 0)),
+    ((ae_sys::PF_AE140_PLUG_IN_VERSION,  ae_sys::PF_AE140_PLUG_IN_SUBVERS), HostVersion::new(14, 0, 0)),
+    ((ae_sys::PF_AE138_PLUG_IN_VERSION,  ae_sys::PF_AE138_PLUG_IN_SUBVERS), HostVersion::new(13, 8, 0)),
+    ((ae_sys::PF_AE136_PLUG_IN_VERSION,  ae_sys::PF_AE136_PLUG_IN_SUBVERS), HostVersion::new(13, 6, 0)),
+    ((ae_sys::PF_AE135_PLUG_IN_VERSION,  ae_sys::PF_AE135_PLUG_IN_SUBVERS), HostVersion::new(13, 5, 0)),
+    ((ae_sys::PF_AE130_PLUG_IN_VERSION,  ae_sys::PF_AE130_PLUG_IN_SUBVERS), HostVersion::new(13, 0, 0)),
+    ((ae_sys::PF_AE122_PLUG_IN_VERSION,  ae_sys::PF_AE122_PLUG_IN_SUBVERS), HostVersion::new(12, 2, 0)),
+    ((ae_sys::PF_AE121_PLUG_IN_VERSION,  ae_sys::PF_AE121_PLUG_IN_SUBVERS), HostVersion::new(12, 1, 0)),
+    ((ae_sys::PF_AE120_PLUG_IN_VERSION,  ae_sys::PF_AE120_PLUG_IN_SUBVERS), HostVersion::new(12, 0, 0)),
+    ((ae_sys::PF_AE1101_PLUG_IN_VERSION, ae_sys::PF_AE1101_PLUG_IN_SUBVERS), HostVersion::new(11, 0, 1)),
+    ((ae_sys::PF_AE110_PLUG_IN_VERSION,  ae_sys::PF_AE110_PLUG_IN_SUBVERS), HostVersion::new(11, 0, 0)),
+    ((ae_sys::PF_AE105_PLUG_IN_VERSION,  ae_sys::PF_AE105_PLUG_IN_SUBVERS), HostVersion::new(10, 5, 0)),
+    ((ae_sys::PF_AE100_PLUG_IN_VERSION,  ae_sys::PF_AE100_PLUG_IN_SUBVERS), HostVersion::new(10, 0, 0)),
+    ((ae_sys::PF_AE90_PLUG_IN_VERSION,   ae_sys::PF_AE90_PLUG_IN_SUBVERS), HostVersion::new(9, 0, 0)),
+    ((ae_sys::PF_AE80_PLUG_IN_VERSION,   ae_sys::PF_AE80_PLUG_IN_SUBVERS), HostVersion::new(8, 0, 0)),
+    ((ae_sys::PF_AE70_PLUG_IN_VERSION,   ae_sys::PF_AE70_PLUG_IN_SUBVERS), HostVersion::new(7, 0, 0)),
+];
+
+/// Effect API version introduced with After Effects 22.0, with Multi-Frame Rendering and [`Command::GetFlattenedSequenceData`].
+const AE_MFR_SPEC_VERSION: (u32, u32) = (ae_sys::PF_AE220_PLUG_IN_VERSION, ae_sys::PF_AE220_PLUG_IN_SUBVERS);
+/// Effect API version introduced with After Effects 16.0, with the GPU framework ([`Command::GpuDeviceSetup`] and [`Command::SmartRenderGpu`]).
+const AE_GPU_SPEC_VERSION: (u32, u32) = (ae_sys::PF_AE160_PLUG_IN_VERSION, ae_sys::PF_AE160_PLUG_IN_SUBVERS);
+
+/// Host features which aren't available in every host, see [`HostInfo::supports()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HostCapability {
+    /// Rendering several frames at once on different threads, with [`OutFlags2::SupportsThreadedRendering`]. After Effects 22.0 and newer.
+    MultiFrameRendering,
+    /// GPU rendering with [`Command::GpuDeviceSetup`] and [`Command::SmartRenderGpu`]. After Effects 16.0 and newer.
+    ///
+    /// Premiere renders GPU effects with its own GPU filter API instead, see the `premiere` crate.
+    GpuFramework,
+    /// [`Command::SmartPreRender`] and [`Command::SmartRender`].
+    ///
+    /// Premiere renders effects with [`Command::Render`], like in the `sdk_noise` example.
+    SmartRender,
+    /// [`Command::GetFlattenedSequenceData`], with [`OutFlags2::SupportsGetFlattenedSequenceData`]. After Effects 22.0 and newer.
+    GetFlattenedSequenceData,
+}
+
+/// The host application and its version, to check which features are available before using them.
+///
+/// Get it from [`InData::host_info()`], from [`HostInfo::current()`] after [`Command::GlobalSetup`],
+/// or in `can_load()` from the host name and version with [`HostInfo::from_host_strings()`].
+///
+/// ```ignore
+/// let host = in_data.host_info();
+/// if host.supports(ae::HostCapability::MultiFrameRendering) {
+///     out_data.set_out_flag2(OutFlags2::SupportsThreadedRendering, true);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HostInfo {
+    pub product: HostProduct,
+    /// Version of the application, from the host version string passed when the plug-in was loaded.
+    ///
+    /// If that's not available, for After Effects it's the oldest release with [`spec_version`](Self::spec_version), for example `22.0.0` for `13.27`.
+    pub version: Option<HostVersion>,
+    /// Version of the effect API, from [`InData::version()`].
+    pub spec_version: Option<(u32, u32)>,
+}
+
+static LOADER_STRINGS: RwLock<Option<(String, String)>> = RwLock::new(None);
+static CURRENT: RwLock<Option<HostInfo>> = RwLock::new(None);
+
+impl HostInfo {
+    /// From the host name and version passed to the plug-in data entry point, like in `can_load()`.
+    pub fn from_host_strings(host_name: &str, host_version: &str) -> Self {
+        Self {
+            product: HostProduct::from_host_name(host_name),
+            version: HostVersion::parse(host_version),
+            spec_version: None,
+        }
+    }
+
+    /// From the application ID and the effect API version in `in_data`, and the strings passed when the plug-in was loaded, if any.
+    pub fn from_in_data(in_data: &InData) -> Self {
+        let product = HostProduct::from_application_id(in_data.application_id());
+        let (major, minor) = in_data.version();
+        let spec_version = (major > 0).then_some((major as u32, minor as u32));
+
+        let loaded = LOADER_STRINGS.read().ok().and_then(|x| x.as_ref().map(|(name, version)| Self::from_host_strings(name, version)));
+        let version = match loaded {
+            Some(loaded) if loaded.product == product && loaded.version.is_some() => loaded.version,
+            _ if product == HostProduct::AfterEffects => spec_version.and_then(Self::ae_version_for_spec),
+            _ => None,
+        };
+        Self { product, version, spec_version }
+    }
+
+    /// The host info stored at [`Command::GlobalSetup`], for code without access to [`InData`].
+    pub fn current() -> Option<Self> {
+        CURRENT.read().ok()?.clone()
+    }
+
+    #[doc(hidden)]
+    pub fn set_loader_strings(host_name: &str, host_version: &str) {
+        if let Ok(mut strings) = LOADER_STRINGS.write() {
+            *strings = Some((host_name.to_owned(), host_version.to_owned()));
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn set_current(info: Self) {
+        if let Ok(mut current) = CURRENT.write() {
+            *current = Some(info);
+        }
+    }
+
+    fn ae_version_for_spec(spec_version: (u32, u32)) -> Option<HostVersion> {
+        AE_SPEC_VERSIONS.iter()
+            .find(|(spec, _)| *spec <= spec_version)
+            .map(|(_, version)| *version)
+    }
+
+    pub fn is_after_effects(&self) -> bool {
+        self.product == HostProduct::AfterEffects
+    }
+
+    pub fn is_premiere(&self) -> bool {
+        self.product == HostProduct::Premiere
+    }
+
+    /// Whether this is After Effects with at least the effect API version `spec_version`, or the application version `version` if the API version isn't known.
+    fn after_effects_at_least(&self, spec_version: (u32, u32), version: (u32, u32)) -> bool {
+        if !self.is_after_effects() {
+            return false;
+        }
+        match (self.spec_version, self.version) {
+            (Some(spec), _) => spec >= spec_version,
+            (None, Some(v)) => v.at_least(version.0, version.1),
+            (None, None) => false,
+        }
+    }
+
+    /// Whether the host has `capability`. Unknown hosts and versions are assumed not to have any.
+    pub fn supports(&self, capability: HostCapability) -> bool {
+        match capability {
+            HostCapability::MultiFrameRendering      => self.after_effects_at_least(AE_MFR_SPEC_VERSION, (22, 0)),
+            HostCapability::GetFlattenedSequenceData => self.after_effects_at_least(AE_MFR_SPEC_VERSION, (22, 0)),
+            HostCapability::GpuFramework             => self.after_effects_at_least(AE_GPU_SPEC_VERSION, (16, 0)),
+            HostCapability::SmartRender              => self.is_after_effects(),
+        }
+    }
+
+    pub fn supports_multi_frame_rendering(&self) -> bool {
+        self.supports(HostCapability::MultiFrameRendering)
+    }
+
+    pub fn supports_gpu_framework(&self) -> bool {
+        self.supports(HostCapability::GpuFramework)
+    }
+
+    pub fn supports_smart_render(&self) -> bool {
+        self.supports(HostCapability::SmartRender)
+    }
+
+    pub fn supports_get_flattened_sequence_data(&self) -> bool {
+        self.supports(HostCapability::GetFlattenedSequenceData)
+    }
+
+    /// Log a warning if the [`current()`](Self::current) host is known not to have `capability`, before calling `api`.
+    pub(crate) fn warn_if_unsupported(capability: HostCapability, api: &str) {
+        if let Some(host) = Self::current() {
+            if !host.supports(capability) {
+                log::warn!("{api} requires {capability:?}, which {:?} {} doesn't support", host.product, host.version.map(|x| x.to_string()).unwrap_or_default());
+            }
+        }
+    }
+}
+
+#[test]
+fn test_host_info() {
+    assert_eq!(HostVersion::parse("24.1.0x46"), Some(HostVersion { major: 24, minor: 1, bug: 0, build: Some(46) }));
+    assert_eq!(HostVersion::parse("23.2 (Build 5)"), Some(HostVersion { major: 23, minor: 2, bug: 0, build: Some(5) }));
+    assert_eq!(HostVersion::parse("beta"), None);
+    assert_eq!(HostVersion::parse("24.1.0x46").unwrap().to_string(), "24.1.0x46");
+
+    let host = HostInfo::from_host_strings("AfterEffects", "21.7");
+    assert!(host.is_after_effects() && !host.supports_multi_frame_rendering() && host.supports_gpu_framework());
+    let host = HostInfo { spec_version: Some((13, 28)), ..host };
+    assert!(host.supports_multi_frame_rendering() && host.supports_get_flattened_sequence_data());
+    assert_eq!(HostInfo::ae_version_for_spec((13, 27)), Some(HostVersion::new(22, 0, 0)));
+    assert_eq!(HostInfo::ae_version_for_spec((13, 15)), Some(HostVersion::new(15, 0, 0)));
+
+    let host = HostInfo::from_host_strings("PremierePro", "25.0");
+    assert!(host.is_premiere() && !host.supports_smart_render() && !host.supports_multi_frame_rendering());
+}
@@ -53,6 +53,11 @@ impl InData {
         unsafe { (*self.ptr).appl_id == i32::from_be_bytes(*b"FXTC") }
     }
 
+    /// The host application, its version and capabilities. See [`HostInfo`].
+    pub fn host_info(&self) -> HostInfo {
+        HostInfo::from_in_data(self)
+    }
+
     /// The current quality setting, either PF_Quality_HI or PF_Quality_LO. Effects should perform faster in LO, and more accurately in HI.
     /// The graphics utility callbacks perform differently between LO and HI quality; so should your effect! This field is defined during all frame and sequence selectors.
     pub fn quality(&self) -> Quality {
@@ -76,37 +76,47 @@ impl Layer {
         }
     }
 
+    /// All rows of the layer as bytes, from the lowest address, which is the last row if [`row_bytes()`](Self::row_bytes) is negative.
+    ///
+    /// Rows are [`buffer_stride()`](Self::buffer_stride) bytes apart and include the [`row_padding_bytes()`](Self::row_padding_bytes),
+    /// except for the row at the end of the buffer, which may not be padded. Use [`view()`](Self::view) to access the pixels.
     pub fn buffer(&self) -> &[u8] {
-        // Stride can be negative, so we need to offset the pointer to get to the real beginning of the buffer
-        let offset = if self.row_bytes() < 0 {
-            self.row_bytes() * (self.height() as isize - 1)
-        } else {
-            0
-        };
+        let (offset, len) = self.buffer_range();
+        if len == 0 {
+            return &[];
+        }
         unsafe {
-            assert!(self.row_bytes().abs() > 0);
             assert!(!self.layer.data.is_null());
-            std::slice::from_raw_parts(
-                (self.layer.data as *const u8).offset(offset),
-                (self.height() as isize * self.row_bytes().abs()) as usize,
-            )
+            std::slice::from_raw_parts((self.layer.data as *const u8).offset(offset), len)
         }
     }
+    /// Mutable version of [`buffer()`](Self::buffer).
     pub fn buffer_mut(&mut self) -> &mut [u8] {
+        let (offset, len) = self.buffer_range();
+        if len == 0 {
+            return &mut [];
+        }
+        unsafe {
+            assert!(!self.layer.data.is_null());
+            std::slice::from_raw_parts_mut((self.layer.data as *mut u8).offset(offset), len)
+        }
+    }
+
+    /// Offset of the lowest row from `data` and the length of the buffer in bytes.
+    fn buffer_range(&self) -> (isize, usize) {
+        if self.width() == 0 || self.height() == 0 {
+            return (0, 0);
+        }
         // Stride can be negative, so we need to offset the pointer to get to the real beginning of the buffer
         let offset = if self.row_bytes() < 0 {
             self.row_bytes() * (self.height() as isize - 1)
         } else {
             0
         };
-        unsafe {
-            assert!(self.row_bytes().abs() > 0);
-            assert!(!self.layer.data.is_null());
-            std::slice::from_raw_parts_mut(
-                (self.layer.data as *mut u8).offset(offset),
-                (self.height() as isize * self.row_bytes().abs()) as usize,
-            )
-        }
+        // The last row only has to hold the pixels, use the whole stride if the pixel format is unknown
+        let stride = self.buffer_stride();
+        let last_row = self.pixel_layout().map_or(stride, |x| x.row_size(self.width()).min(stride));
+        (offset, (self.height() - 1) * stride + last_row)
     }
 
     pub fn copy_from(&mut self, src: &Self, src_rect: Option<Rect>, dst_rect: Option<Rect>) -> Result<(), Error> {
@@ -214,31 +224,34 @@ impl Layer {
                 }
     }
 
-    pub fn as_pixel8_mut(&self, x: usize, y: usize) -> &mut Pixel8 {
-        debug_assert!(x < self.width() && y < self.height(), "Coordinate is outside EffectWorld bounds.");
-        unsafe { &mut *(self.data_ptr_mut().offset(y as isize * self.row_bytes()) as *mut Pixel8).offset(x as isize) }
+    pub fn as_pixel8_mut(&mut self, x: usize, y: usize) -> &mut Pixel8 {
+        assert!(x < self.width() && y < self.height(), "Coordinate is outside EffectWorld bounds.");
+        unsafe { &mut *(self.data_ptr_mut().offset(y as isize * self.row_bytes()) as *mut Pixel8).add(x) }
     }
 
     pub fn as_pixel8(&self, x: usize, y: usize) -> &Pixel8 {
-        self.as_pixel8_mut(x, y)
+        assert!(x < self.width() && y < self.height(), "Coordinate is outside EffectWorld bounds.");
+        unsafe { &*(self.data_ptr().offset(y as isize * self.row_bytes()) as *const Pixel8).add(x) }
     }
 
-    pub fn as_pixel16_mut(&self, x: usize, y: usize) -> &mut Pixel16 {
-        debug_assert!(x < self.width() && y < self.height(), "Coordinate is outside EffectWorld bounds.");
-        unsafe { &mut *(self.data_ptr_mut().offset(y as isize * self.row_bytes()) as *mut Pixel16).offset(x as isize) }
+    pub fn as_pixel16_mut(&mut self, x: usize, y: usize) -> &mut Pixel16 {
+        assert!(x < self.width() && y < self.height(), "Coordinate is outside EffectWorld bounds.");
+        unsafe { &mut *(self.data_ptr_mut().offset(y as isize * self.row_bytes()) as *mut Pixel16).add(x) }
     }
 
     pub fn as_pixel16(&self, x: usize, y: usize) -> &Pixel16 {
-        self.as_pixel16_mut(x, y)
+        assert!(x < self.width() && y < self.height(), "Coordinate is outside EffectWorld bounds.");
+        unsafe { &*(self.data_ptr().offset(y as isize * self.row_bytes()) as *const Pixel16).add(x) }
     }
 
-    pub fn as_pixel32_mut(&self, x: usize, y: usize) -> &mut PixelF32 {
-        debug_assert!(x < self.width() && y < self.height(), "Coordinate is outside EffectWorld bounds.");
+    pub fn as_pixel32_mut(&mut self, x: usize, y: usize) -> &mut PixelF32 {
+        assert!(x < self.width() && y < self.height(), "Coordinate is outside EffectWorld bounds.");
         unsafe { &mut *(self.data_ptr_mut().offset(y as isize * self.row_bytes()) as *mut PixelF32).add(x) }
     }
 
     pub fn as_pixel32(&self, x: usize, y: usize) -> &PixelF32 {
-        self.as_pixel32_mut(x, y)
+        assert!(x < self.width() && y < self.height(), "Coordinate is outside EffectWorld bounds.");
+        unsafe { &*(self.data_ptr().offset(y as isize * self.row_bytes()) as *const PixelF32).add(x) }
     }
 
     pub fn world_type(&self) -> aegp::WorldType {
@@ -260,7 +273,7 @@ impl Layer {
         } else if flags.contains(WorldFlags::RESERVED1) {
             32
         } else {
-            if InData::from_raw(self.in_data_ptr).is_premiere() {
+            if !self.in_data_ptr.is_null() && InData::from_raw(self.in_data_ptr).is_premiere() {
                 match self.pr_pixel_format() {
                     Ok(pr::PixelFormat::Rgb444_10u) |
                     Ok(pr::PixelFormat::V210422_10u601) |
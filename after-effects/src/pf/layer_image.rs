@@ -0,0 +1,93 @@
+use super::*;
+use image::{ DynamicImage, ImageBuffer, ImageFormat, Rgba };
+use std::path::Path;
+
+impl Layer {
+    /// Copy the pixels to an [`image::DynamicImage`], converted to RGBA.
+    ///
+    /// 8 bpc layers become `ImageRgba8`, 16 bpc layers `ImageRgba16` (rescaled from `0..=32768` to `0..=65535`)
+    /// and all other layers `ImageRgba32F`. Premiere pixel formats are converted using [`pixel_layout()`](Self::pixel_layout).
+    pub fn to_image(&self) -> Result<DynamicImage, Error> {
+        let (width, height) = (self.width(), self.height());
+        if width == 0 || height == 0 {
+            return Ok(match self.bit_depth() {
+                8  => DynamicImage::ImageRgba8(ImageBuffer::new(width as u32, height as u32)),
+                16 => DynamicImage::ImageRgba16(ImageBuffer::new(width as u32, height as u32)),
+                _  => DynamicImage::ImageRgba32F(ImageBuffer::new(width as u32, height as u32)),
+            });
+        }
+        let mut rgba = vec![0.0f32; width * height * 4];
+        let mut row = vec![PixelF32 { alpha: 0.0, red: 0.0, green: 0.0, blue: 0.0 }; width];
+        for (y, dst) in rgba.chunks_exact_mut(width * 4).enumerate() {
+            self.read_rgba_row(y, &mut row)?;
+            for (px, dst) in row.iter().zip(dst.chunks_exact_mut(4)) {
+                dst.copy_from_slice(&[px.red, px.green, px.blue, px.alpha]);
+            }
+        }
+        let image = ImageBuffer::<Rgba<f32>, _>::from_raw(width as u32, height as u32, rgba).ok_or(Error::BadCallbackParameter)?;
+        Ok(match self.bit_depth() {
+            8  => DynamicImage::ImageRgba32F(image).into_rgba8().into(),
+            16 => DynamicImage::ImageRgba32F(image).into_rgba16().into(),
+            _  => DynamicImage::ImageRgba32F(image),
+        })
+    }
+
+    /// Copy the pixels of `image` to this layer, converting to the bit depth and channel order of the layer.
+    ///
+    /// Returns [`Error::BadCallbackParameter`] if the image size doesn't match the layer.
+    pub fn copy_from_image(&mut self, image: &DynamicImage) -> Result<(), Error> {
+        let (width, height) = (self.width(), self.height());
+        if image.width() as usize != width || image.height() as usize != height {
+            return Err(Error::BadCallbackParameter);
+        }
+        if width == 0 || height == 0 {
+            return Ok(());
+        }
+        let rgba = image.to_rgba32f();
+        let mut row = vec![PixelF32 { alpha: 0.0, red: 0.0, green: 0.0, blue: 0.0 }; width];
+        for (y, src) in rgba.as_raw().chunks_exact(width * 4).enumerate() {
+            for (px, src) in row.iter_mut().zip(src.chunks_exact(4)) {
+                *px = PixelF32 { red: src[0], green: src[1], blue: src[2], alpha: src[3] };
+            }
+            self.write_rgba_row(y, &row)?;
+        }
+        Ok(())
+    }
+
+    /// Save the layer to a PNG file, for debugging. 16 and 32 bpc layers are saved as 16-bit PNG.
+    pub fn dump_png(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let image = match self.to_image()? {
+            DynamicImage::ImageRgba32F(image) => DynamicImage::ImageRgba32F(image).into_rgba16().into(),
+            image => image,
+        };
+        Self::save_image(&image, path.as_ref(), ImageFormat::Png)
+    }
+
+    /// Save the layer to an OpenEXR file, for debugging. Values are stored as 32-bit float, without any color conversion.
+    pub fn dump_exr(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let image = DynamicImage::ImageRgba32F(self.to_image()?.into_rgba32f());
+        Self::save_image(&image, path.as_ref(), ImageFormat::OpenExr)
+    }
+
+    fn save_image(image: &DynamicImage, path: &Path, format: ImageFormat) -> Result<(), Error> {
+        image.save_with_format(path, format).map_err(|e| {
+            log::error!("Failed to save {}: {e}", path.display());
+            Error::Generic
+        })
+    }
+}
+
+#[test]
+fn test_layer_image_empty() {
+    // A collapsed 0x3 layer, like an empty output rect
+    let mut def: ae_sys::PF_LayerDef = unsafe { std::mem::zeroed() };
+    def.height = 3;
+    def.world_flags = WorldFlags::DEEP.bits() as _;
+    let mut layer = Layer::from_raw(&mut def, std::ptr::null::<ae_sys::PF_InData>(), None);
+
+    let image = layer.to_image().unwrap();
+    assert!(matches!(image, DynamicImage::ImageRgba16(_)));
+    assert_eq!((image.width(), image.height()), (0, 3));
+    assert_eq!(layer.copy_from_image(&image), Ok(()));
+    assert_eq!(layer.copy_from_image(&DynamicImage::new_rgba8(1, 3)), Err(Error::BadCallbackParameter));
+}
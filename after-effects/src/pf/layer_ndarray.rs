@@ -0,0 +1,69 @@
+use super::*;
+use ndarray::{ ArrayView3, ArrayViewMut3, Axis, ShapeBuilder };
+
+impl Layer {
+    /// View the pixels as an `ndarray` of shape `(height, width, 4)`, without copying.
+    ///
+    /// The last axis is in the After Effects channel order: alpha, red, green, blue. The row stride is taken from [`row_bytes()`](Self::row_bytes),
+    /// so row padding isn't part of the view. Returns [`Error::BadCallbackParameter`] if the bit depth of the layer doesn't match `P`.
+    pub fn as_array_view<P: PixelType>(&self) -> Result<ArrayView3<'_, P::Channel>, Error> {
+        let view = self.view::<P>()?;
+        let (ptr, stride, flip) = self.array_layout::<P>(view.width(), view.height())?;
+        let shape = (view.height(), view.width(), 4).strides((stride, 4, 1));
+        let mut array = unsafe { ArrayView3::from_shape_ptr(shape, ptr as *const P::Channel) };
+        if flip {
+            array.invert_axis(Axis(0));
+        }
+        Ok(array)
+    }
+
+    /// Mutable version of [`as_array_view()`](Self::as_array_view).
+    pub fn as_array_view_mut<P: PixelType>(&mut self) -> Result<ArrayViewMut3<'_, P::Channel>, Error> {
+        let (width, height) = {
+            let view = self.view_mut::<P>()?;
+            (view.width(), view.height())
+        };
+        let (ptr, stride, flip) = self.array_layout::<P>(width, height)?;
+        let shape = (height, width, 4).strides((stride, 4, 1));
+        let mut array = unsafe { ArrayViewMut3::from_shape_ptr(shape, ptr as *mut P::Channel) };
+        if flip {
+            array.invert_axis(Axis(0));
+        }
+        Ok(array)
+    }
+
+    /// Pointer to the top-most row in memory, row stride in channels and whether the rows are stored bottom-up.
+    fn array_layout<P: PixelType>(&self, width: usize, height: usize) -> Result<(*mut u8, usize, bool), Error> {
+        let channel_size = std::mem::size_of::<P::Channel>();
+        let row_bytes = self.row_bytes();
+        if !row_bytes.unsigned_abs().is_multiple_of(channel_size) || width == 0 || height == 0 {
+            return Err(Error::BadCallbackParameter);
+        }
+        let data = unsafe { self.data_ptr_mut() };
+        if row_bytes < 0 {
+            // ndarray doesn't accept negative strides here, so start at the last row and flip the axis afterwards
+            Ok((unsafe { data.offset((height as isize - 1) * row_bytes) }, row_bytes.unsigned_abs() / channel_size, true))
+        } else {
+            Ok((data, row_bytes as usize / channel_size, false))
+        }
+    }
+}
+
+#[test]
+fn test_array_view() {
+    let (width, height, row_bytes) = (3usize, 2usize, 32usize);
+    let mut buffer: Vec<u16> = (0..(row_bytes / 2 * height) as u16).collect();
+    let mut def: ae_sys::PF_LayerDef = unsafe { std::mem::zeroed() };
+    def.data = buffer.as_mut_ptr() as *mut _;
+    def.rowbytes = row_bytes as _;
+    def.width = width as _;
+    def.height = height as _;
+    def.world_flags = WorldFlags::DEEP.bits() as _;
+    let layer = Layer::from_owned(def, std::ptr::null::<ae_sys::PF_InData>(), |_| {});
+
+    let array = layer.as_array_view::<Pixel16>().unwrap();
+    assert_eq!(array.shape(), [2, 3, 4]);
+    // Second row, third pixel, red channel. The padding at the end of the first row is skipped.
+    assert_eq!(array[[1, 2, 1]], 16 + 2 * 4 + 1);
+    assert!(layer.as_array_view::<Pixel8>().is_err());
+}
@@ -0,0 +1,261 @@
+use super::*;
+use parking_lot::Mutex;
+use std::sync::atomic::{ AtomicBool, AtomicUsize, Ordering };
+
+/// Threads used by [`Layer::par_process_rows()`] and [`Layer::par_process_tiles()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreadPool {
+    /// After Effects' thread pool, using [`UtilCallbacks::iterate_generic()`].
+    Host,
+    /// The global rayon thread pool.
+    #[cfg(feature = "rayon")]
+    Rayon,
+    /// Process everything on the calling thread.
+    None,
+}
+
+/// Options for [`Layer::par_process_rows_with()`] and [`Layer::par_process_tiles_with()`].
+#[derive(Debug, Clone, Copy)]
+pub struct ProcessOptions {
+    pub thread_pool: ThreadPool,
+    /// Number of rows processed as one unit of work. Progress is reported and abort is checked after every unit.
+    ///
+    /// Ignored by the tile functions, where every tile is a unit of work.
+    pub rows_per_chunk: usize,
+    /// Report progress to the host with [`InteractCallbacks::progress()`]. Abort is checked either way.
+    pub report_progress: bool,
+}
+
+impl Default for ProcessOptions {
+    fn default() -> Self {
+        Self {
+            thread_pool: ThreadPool::Host,
+            rows_per_chunk: 16,
+            report_progress: true,
+        }
+    }
+}
+
+impl Layer {
+    /// Process the layer row by row into `output` on multiple threads, with the default [`ProcessOptions`].
+    ///
+    /// The callback gets the row index and the matching rows of this layer and `output`.
+    /// Both layers must have the same size and the bit depth of `P`, otherwise [`Error::BadCallbackParameter`] is returned.
+    ///
+    /// Returns [`Error::InterruptCancel`] (or the error reported by the host) if the user cancelled the render.
+    ///
+    /// ```ignore
+    /// in_layer.par_process_rows::<PixelF32, _>(&mut out_layer, |_y, in_row, out_row| {
+    ///     for (src, dst) in in_row.iter().zip(out_row) {
+    ///         *dst = PixelF32 { alpha: src.alpha, red: 1.0 - src.red, green: 1.0 - src.green, blue: 1.0 - src.blue };
+    ///     }
+    ///     Ok(())
+    /// })?;
+    /// ```
+    pub fn par_process_rows<P, F>(&self, output: &mut Layer, cb: F) -> Result<(), Error>
+    where
+        P: PixelType,
+        F: Fn(usize, &[P], &mut [P]) -> Result<(), Error> + Sync,
+    {
+        self.par_process_rows_with(output, ProcessOptions::default(), cb)
+    }
+
+    /// Same as [`par_process_rows()`](Self::par_process_rows), with custom options.
+    pub fn par_process_rows_with<P, F>(&self, output: &mut Layer, options: ProcessOptions, cb: F) -> Result<(), Error>
+    where
+        P: PixelType,
+        F: Fn(usize, &[P], &mut [P]) -> Result<(), Error> + Sync,
+    {
+        let in_data_ptr = self.in_data_ptr;
+        let input = self.view::<P>()?;
+        let mut output = output.view_mut::<P>()?;
+        if input.width() != output.width() || input.height() != output.height() {
+            return Err(Error::BadCallbackParameter);
+        }
+        let chunks: Vec<_> = output.row_chunks_mut(options.rows_per_chunk.max(1)).collect();
+
+        run_parallel(in_data_ptr, &options, chunks, |mut chunk| {
+            let first_row = chunk.first_row();
+            for (y, out_row) in chunk.rows_mut().enumerate() {
+                cb(first_row + y, input.row(first_row + y), out_row)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Process the layer in tiles of `tile_width` x `tile_height` pixels into `output` on multiple threads, with the default [`ProcessOptions`].
+    ///
+    /// The callback gets the area of this layer and the matching area of `output`. Use [`LayerViewMut::first_row()`] and
+    /// [`LayerViewMut::first_column()`] to get the position of the tile. Tiles at the right and bottom edges may be smaller.
+    ///
+    /// Both layers must have the same size and the bit depth of `P`, otherwise [`Error::BadCallbackParameter`] is returned.
+    pub fn par_process_tiles<P, F>(&self, output: &mut Layer, tile_width: usize, tile_height: usize, cb: F) -> Result<(), Error>
+    where
+        P: PixelType,
+        F: Fn(LayerView<P>, LayerViewMut<P>) -> Result<(), Error> + Sync,
+    {
+        self.par_process_tiles_with(output, tile_width, tile_height, ProcessOptions::default(), cb)
+    }
+
+    /// Same as [`par_process_tiles()`](Self::par_process_tiles), with custom options.
+    pub fn par_process_tiles_with<P, F>(&self, output: &mut Layer, tile_width: usize, tile_height: usize, options: ProcessOptions, cb: F) -> Result<(), Error>
+    where
+        P: PixelType,
+        F: Fn(LayerView<P>, LayerViewMut<P>) -> Result<(), Error> + Sync,
+    {
+        let in_data_ptr = self.in_data_ptr;
+        let input = self.view::<P>()?;
+        let mut output = output.view_mut::<P>()?;
+        if input.width() != output.width() || input.height() != output.height() {
+            return Err(Error::BadCallbackParameter);
+        }
+        let tiles: Vec<_> = output.tiles_mut(tile_width.max(1), tile_height.max(1)).collect();
+
+        run_parallel(in_data_ptr, &options, tiles, |tile| {
+            let area = input.sub_view(tile.first_column(), tile.first_row(), tile.width(), tile.height());
+            cb(area, tile)
+        })
+    }
+}
+
+/// Runs `process` for every item, checking abort and reporting progress from the thread which is allowed to call back into the host.
+///
+/// Progress is reported in pixels, so rows and tiles are handled the same way.
+fn run_parallel<P, F>(in_data_ptr: *const ae_sys::PF_InData, options: &ProcessOptions, items: Vec<LayerViewMut<P>>, process: F) -> Result<(), Error>
+where
+    P: PixelType,
+    F: Fn(LayerViewMut<P>) -> Result<(), Error> + Sync,
+{
+    let total: usize = items.iter().map(|x| x.width() * x.height()).sum();
+
+    let interact = (!in_data_ptr.is_null()).then(|| InData::from_raw(in_data_ptr).interact());
+    let check = |done: usize| -> Result<(), Error> {
+        match &interact {
+            Some(interact) if options.report_progress => interact.progress(done.min(i32::MAX as usize) as i32, total.min(i32::MAX as usize) as i32),
+            Some(interact) => interact.abort(),
+            None => Ok(()),
+        }
+    };
+
+    match options.thread_pool {
+        ThreadPool::None => {
+            let mut done = 0;
+            for item in items {
+                done += item.width() * item.height();
+                process(item)?;
+                check(done)?;
+            }
+            Ok(())
+        }
+        #[cfg(feature = "rayon")]
+        ThreadPool::Rayon => {
+            use rayon::prelude::*;
+            // Process batches of items on the pool and call back into the host between them, from the calling thread.
+            let mut items = items;
+            let batch_size = rayon::current_num_threads().max(1);
+            let mut done = 0;
+            while !items.is_empty() {
+                let batch: Vec<_> = items.drain(..batch_size.min(items.len())).collect();
+                done += batch.iter().map(|x| x.width() * x.height()).sum::<usize>();
+                batch.into_par_iter().try_for_each(&process)?;
+                check(done)?;
+            }
+            Ok(())
+        }
+        ThreadPool::Host => {
+            if in_data_ptr.is_null() {
+                return Err(Error::BadCallbackParameter);
+            }
+            let count = items.len();
+            let items: Vec<_> = items.into_iter().map(|x| Mutex::new(Some(x))).collect();
+            let done = AtomicUsize::new(0);
+            let aborted = AtomicBool::new(false);
+            // `iterate_generic` doesn't require `Sync`, so the compiler doesn't check this closure.
+            // It's called from several host threads at once, which is sound because `process` is `Sync`
+            // and every item is taken out of its `Mutex` by exactly one thread.
+            UtilCallbacks::new(in_data_ptr).iterate_generic(count as i32, |thread_index, i, _| {
+                if aborted.load(Ordering::Relaxed) {
+                    return Err(Error::InterruptCancel);
+                }
+                let result = (|| {
+                    let item = items[i as usize].lock().take().ok_or(Error::BadCallbackParameter)?;
+                    let done = done.fetch_add(item.width() * item.height(), Ordering::Relaxed) + item.width() * item.height();
+                    process(item)?;
+                    // Only the first thread is allowed to call the abort and progress callbacks.
+                    if thread_index == 0 {
+                        check(done)?;
+                    }
+                    Ok(())
+                })();
+                // Stop the other threads on any error
+                if result.is_err() {
+                    aborted.store(true, Ordering::Relaxed);
+                }
+                result
+            })
+        }
+    }
+}
+
+#[test]
+fn test_par_process() {
+    // 5x3 float layers with a padding pixel at the end of each row, without a host
+    let layer_def = |buffer: &mut [PixelF32]| {
+        let mut def: ae_sys::PF_LayerDef = unsafe { std::mem::zeroed() };
+        def.data = buffer.as_mut_ptr() as *mut _;
+        def.rowbytes = (std::mem::size_of::<PixelF32>() * 6) as _;
+        def.width = 5;
+        def.height = 3;
+        def.world_flags = WorldFlags::RESERVED1.bits() as _;
+        def
+    };
+    let null_in_data = std::ptr::null::<ae_sys::PF_InData>();
+    let options = ProcessOptions { thread_pool: ThreadPool::None, rows_per_chunk: 2, report_progress: true };
+
+    let mut input_pixels: Vec<_> = (0..18).map(|i| PixelF32 { alpha: 1.0, red: i as f32, green: 0.0, blue: 0.0 }).collect();
+    let mut output_pixels = vec![PixelF32 { alpha: 0.0, red: -1.0, green: -1.0, blue: -1.0 }; 18];
+    let mut input_def = layer_def(&mut input_pixels);
+    let mut output_def = layer_def(&mut output_pixels);
+    let input = Layer::from_raw(&mut input_def, null_in_data, None);
+    let mut output = Layer::from_raw(&mut output_def, null_in_data, None);
+
+    input.par_process_rows_with::<PixelF32, _>(&mut output, options, |y, in_row, out_row| {
+        for (src, dst) in in_row.iter().zip(out_row) {
+            *dst = PixelF32 { green: y as f32, ..*src };
+        }
+        Ok(())
+    }).unwrap();
+    let view = output.view::<PixelF32>().unwrap();
+    assert!((0..3).all(|y| (0..5).all(|x| view.pixel(x, y).red == (y * 6 + x) as f32 && view.pixel(x, y).green == y as f32)));
+
+    // Tiles at the right and bottom edges are smaller, every pixel is visited once
+    input.par_process_tiles_with::<PixelF32, _>(&mut output, 2, 2, options, |area, mut tile| {
+        assert_eq!((area.width(), area.height()), (tile.width(), tile.height()));
+        let (x0, y0) = (tile.first_column(), tile.first_row());
+        for (y, out_row) in tile.rows_mut().enumerate() {
+            for (x, dst) in out_row.iter_mut().enumerate() {
+                *dst = PixelF32 { blue: dst.blue + 1.0, green: (x0 * 10 + y0) as f32, ..*area.pixel(x, y) };
+            }
+        }
+        Ok(())
+    }).unwrap();
+    let view = output.view::<PixelF32>().unwrap();
+    assert!((0..3).all(|y| (0..5).all(|x| view.pixel(x, y).blue == 1.0 && view.pixel(x, y).red == (y * 6 + x) as f32 && view.pixel(x, y).green == (x / 2 * 20 + y / 2 * 2) as f32)));
+    // The padding isn't touched
+    drop(output);
+    assert!(output_pixels.chunks(6).all(|row| row[5].red == -1.0));
+
+    // Errors from the callback stop the processing
+    let mut output = Layer::from_raw(&mut output_def, null_in_data, None);
+    let calls = AtomicUsize::new(0);
+    let result = input.par_process_rows_with::<PixelF32, _>(&mut output, options, |_, _, _| {
+        calls.fetch_add(1, Ordering::Relaxed);
+        Err(Error::InterruptCancel)
+    });
+    assert_eq!(result, Err(Error::InterruptCancel));
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+    // The host pool needs the host
+    let result = input.par_process_tiles_with::<PixelF32, _>(&mut output, 2, 2, ProcessOptions { thread_pool: ThreadPool::Host, ..options }, |_, _| Ok(()));
+    assert_eq!(result, Err(Error::BadCallbackParameter));
+}
@@ -0,0 +1,454 @@
+use super::*;
+use std::marker::PhantomData;
+
+/// Read-only typed view of the pixels of a [`Layer`], created with [`Layer::view()`].
+///
+/// Rows are addressed using the layer's `row_bytes`, so row padding and negative strides are handled.
+#[derive(Clone, Copy)]
+pub struct LayerView<'a, P: PixelType> {
+    data: *const u8,
+    row_bytes: isize,
+    width: usize,
+    height: usize,
+    first_row: usize,
+    first_column: usize,
+    _marker: PhantomData<&'a [P]>,
+}
+
+/// Mutable typed view of the pixels of a [`Layer`], created with [`Layer::view_mut()`].
+///
+/// Can be split into disjoint row chunks with [`row_chunks_mut()`](Self::row_chunks_mut) or [`split_at_row_mut()`](Self::split_at_row_mut),
+/// to process them on multiple threads.
+pub struct LayerViewMut<'a, P: PixelType> {
+    data: *mut u8,
+    row_bytes: isize,
+    width: usize,
+    height: usize,
+    first_row: usize,
+    first_column: usize,
+    _marker: PhantomData<&'a mut [P]>,
+}
+
+unsafe impl<P: PixelType> Send for LayerView<'_, P> {}
+unsafe impl<P: PixelType> Sync for LayerView<'_, P> {}
+unsafe impl<P: PixelType> Send for LayerViewMut<'_, P> {}
+unsafe impl<P: PixelType> Sync for LayerViewMut<'_, P> {}
+
+impl<P: PixelType> Debug for LayerView<'_, P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LayerView")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("row_bytes", &self.row_bytes)
+            .field("first_row", &self.first_row)
+            .field("first_column", &self.first_column)
+            .field("bit_depth", &P::BIT_DEPTH)
+            .finish()
+    }
+}
+impl<P: PixelType> Debug for LayerViewMut<'_, P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LayerViewMut")
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("row_bytes", &self.row_bytes)
+            .field("first_row", &self.first_row)
+            .field("first_column", &self.first_column)
+            .field("bit_depth", &P::BIT_DEPTH)
+            .finish()
+    }
+}
+
+fn check_layout<P: PixelType>(data: *const u8, row_bytes: isize, width: usize, height: usize) -> Result<(), Error> {
+    if width > 0 && height > 0 && (data.is_null() || row_bytes.unsigned_abs() < width * std::mem::size_of::<P>()) {
+        return Err(Error::BadCallbackParameter);
+    }
+    if !(data as usize).is_multiple_of(std::mem::align_of::<P>()) || !row_bytes.unsigned_abs().is_multiple_of(std::mem::align_of::<P>()) {
+        return Err(Error::BadCallbackParameter);
+    }
+    Ok(())
+}
+
+impl<'a, P: PixelType> LayerView<'a, P> {
+    /// Create a view of raw pixel memory.
+    ///
+    /// # Safety
+    /// `data` must point to `height` rows of at least `width` pixels of type `P`, `row_bytes` apart,
+    /// which stay valid and are not mutated for the lifetime `'a`.
+    pub unsafe fn from_raw_parts(data: *const u8, row_bytes: isize, width: usize, height: usize) -> Result<Self, Error> {
+        check_layout::<P>(data, row_bytes, width, height)?;
+        Ok(Self { data, row_bytes, width, height, first_row: 0, first_column: 0, _marker: PhantomData })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    pub fn row_bytes(&self) -> isize {
+        self.row_bytes
+    }
+    /// Index of the first row of this view in the layer it was created from. Non-zero for row chunks.
+    pub fn first_row(&self) -> usize {
+        self.first_row
+    }
+    /// Index of the first column of this view in the layer it was created from. Non-zero for tiles.
+    pub fn first_column(&self) -> usize {
+        self.first_column
+    }
+
+    /// Pixels of row `y`, relative to this view. Panics if `y` is out of bounds.
+    pub fn row(&self, y: usize) -> &'a [P] {
+        assert!(y < self.height, "Row {y} is outside of the view with height {}", self.height);
+        unsafe { std::slice::from_raw_parts(self.data.offset(y as isize * self.row_bytes) as *const P, self.width) }
+    }
+
+    /// Pixel at `x`, `y`, relative to this view. Panics if the coordinate is out of bounds.
+    pub fn pixel(&self, x: usize, y: usize) -> &'a P {
+        &self.row(y)[x]
+    }
+
+    /// Iterator over all rows, from top to bottom.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &'a [P]> + ExactSizeIterator + '_ {
+        (0..self.height).map(move |y| self.row(y))
+    }
+
+    /// View of the `width` x `height` area at `x`, `y`, relative to this view. Panics if the area is out of bounds.
+    pub fn sub_view(&self, x: usize, y: usize, width: usize, height: usize) -> LayerView<'a, P> {
+        assert!(x + width <= self.width && y + height <= self.height, "Area {x},{y} {width}x{height} is outside of the view with size {}x{}", self.width, self.height);
+        LayerView {
+            data: unsafe { self.data.offset(y as isize * self.row_bytes).add(x * std::mem::size_of::<P>()) },
+            row_bytes: self.row_bytes,
+            width,
+            height,
+            first_row: self.first_row + y,
+            first_column: self.first_column + x,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, P: PixelType> LayerViewMut<'a, P> {
+    /// Create a mutable view of raw pixel memory.
+    ///
+    /// # Safety
+    /// `data` must point to `height` rows of at least `width` pixels of type `P`, `row_bytes` apart,
+    /// which stay valid and are not accessed through any other pointer for the lifetime `'a`.
+    pub unsafe fn from_raw_parts(data: *mut u8, row_bytes: isize, width: usize, height: usize) -> Result<Self, Error> {
+        check_layout::<P>(data, row_bytes, width, height)?;
+        Ok(Self { data, row_bytes, width, height, first_row: 0, first_column: 0, _marker: PhantomData })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+    pub fn height(&self) -> usize {
+        self.height
+    }
+    pub fn row_bytes(&self) -> isize {
+        self.row_bytes
+    }
+    /// Index of the first row of this view in the layer it was created from. Non-zero for row chunks.
+    pub fn first_row(&self) -> usize {
+        self.first_row
+    }
+    /// Index of the first column of this view in the layer it was created from. Non-zero for tiles.
+    pub fn first_column(&self) -> usize {
+        self.first_column
+    }
+
+    /// Reborrow as a read-only view.
+    pub fn as_view(&self) -> LayerView<'_, P> {
+        LayerView {
+            data: self.data,
+            row_bytes: self.row_bytes,
+            width: self.width,
+            height: self.height,
+            first_row: self.first_row,
+            first_column: self.first_column,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Pixels of row `y`, relative to this view. Panics if `y` is out of bounds.
+    pub fn row(&self, y: usize) -> &[P] {
+        self.as_view().row(y)
+    }
+    /// Mutable pixels of row `y`, relative to this view. Panics if `y` is out of bounds.
+    pub fn row_mut(&mut self, y: usize) -> &mut [P] {
+        assert!(y < self.height, "Row {y} is outside of the view with height {}", self.height);
+        unsafe { std::slice::from_raw_parts_mut(self.data.offset(y as isize * self.row_bytes) as *mut P, self.width) }
+    }
+
+    /// Pixel at `x`, `y`, relative to this view. Panics if the coordinate is out of bounds.
+    pub fn pixel(&self, x: usize, y: usize) -> &P {
+        &self.row(y)[x]
+    }
+    /// Mutable pixel at `x`, `y`, relative to this view. Panics if the coordinate is out of bounds.
+    pub fn pixel_mut(&mut self, x: usize, y: usize) -> &mut P {
+        &mut self.row_mut(y)[x]
+    }
+
+    /// Iterator over all rows, from top to bottom.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[P]> + ExactSizeIterator + '_ {
+        (0..self.height).map(move |y| self.row(y))
+    }
+    /// Iterator over all mutable rows, from top to bottom.
+    pub fn rows_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut [P]> + ExactSizeIterator + '_ {
+        let (data, row_bytes, width) = (self.data, self.row_bytes, self.width);
+        // Every row is a disjoint memory range, so handing out all of them at once is fine.
+        (0..self.height).map(move |y| unsafe { std::slice::from_raw_parts_mut(data.offset(y as isize * row_bytes) as *mut P, width) })
+    }
+
+    /// Split the view into two disjoint views: rows `0..y` and `y..height`. Panics if `y > height`.
+    pub fn split_at_row_mut(&mut self, y: usize) -> (LayerViewMut<'_, P>, LayerViewMut<'_, P>) {
+        assert!(y <= self.height, "Row {y} is outside of the view with height {}", self.height);
+        let top = LayerViewMut {
+            data: self.data,
+            row_bytes: self.row_bytes,
+            width: self.width,
+            height: y,
+            first_row: self.first_row,
+            first_column: self.first_column,
+            _marker: PhantomData,
+        };
+        let bottom = LayerViewMut {
+            data: unsafe { self.data.offset(y as isize * self.row_bytes) },
+            row_bytes: self.row_bytes,
+            width: self.width,
+            height: self.height - y,
+            first_row: self.first_row + y,
+            first_column: self.first_column,
+            _marker: PhantomData,
+        };
+        (top, bottom)
+    }
+
+    /// Split the view into disjoint chunks of `rows_per_chunk` rows. The last chunk may be smaller.
+    ///
+    /// The chunks can be sent to different threads. Use [`first_row()`](Self::first_row) to get the position of a chunk in the layer.
+    pub fn row_chunks_mut(&mut self, rows_per_chunk: usize) -> impl ExactSizeIterator<Item = LayerViewMut<'_, P>> + '_ {
+        assert!(rows_per_chunk > 0);
+        let (data, row_bytes, width, height, first_row, first_column) = (self.data, self.row_bytes, self.width, self.height, self.first_row, self.first_column);
+        (0..height.div_ceil(rows_per_chunk)).map(move |i| {
+            let y = i * rows_per_chunk;
+            LayerViewMut {
+                data: unsafe { data.offset(y as isize * row_bytes) },
+                row_bytes,
+                width,
+                height: rows_per_chunk.min(height - y),
+                first_row: first_row + y,
+                first_column,
+                _marker: PhantomData,
+            }
+        })
+    }
+
+    /// Split the view into disjoint tiles of `tile_width` x `tile_height` pixels, row by row. Tiles at the right and bottom edges may be smaller.
+    ///
+    /// Use [`first_row()`](Self::first_row) and [`first_column()`](Self::first_column) to get the position of a tile in the layer.
+    pub fn tiles_mut(&mut self, tile_width: usize, tile_height: usize) -> impl ExactSizeIterator<Item = LayerViewMut<'_, P>> + '_ {
+        assert!(tile_width > 0 && tile_height > 0);
+        let (data, row_bytes, width, height, first_row, first_column) = (self.data, self.row_bytes, self.width, self.height, self.first_row, self.first_column);
+        let columns = width.div_ceil(tile_width);
+        (0..columns * height.div_ceil(tile_height)).map(move |i| {
+            let (x, y) = ((i % columns) * tile_width, (i / columns) * tile_height);
+            // Tiles only hand out `width` pixels of each row, so tiles next to each other don't overlap.
+            LayerViewMut {
+                data: unsafe { data.offset(y as isize * row_bytes).add(x * std::mem::size_of::<P>()) },
+                row_bytes,
+                width: tile_width.min(width - x),
+                height: tile_height.min(height - y),
+                first_row: first_row + y,
+                first_column: first_column + x,
+                _marker: PhantomData,
+            }
+        })
+    }
+}
+
+/// Code which is generic over the [`PixelType`], called by [`Layer::dispatch_depth()`].
+///
+/// Rust closures can't be generic, so implement this trait on a struct which holds the arguments instead.
+pub trait DispatchDepth {
+    type Output;
+    fn call<P: PixelType>(self, view: LayerView<'_, P>) -> Self::Output;
+}
+
+/// Code which is generic over the [`PixelType`], called by [`Layer::dispatch_depth_mut()`].
+pub trait DispatchDepthMut {
+    type Output;
+    fn call<P: PixelType>(self, view: LayerViewMut<'_, P>) -> Self::Output;
+}
+
+impl Layer {
+    /// Call `f` with a view of the pixel type matching the bit depth of the layer.
+    ///
+    /// Returns [`Error::BadCallbackParameter`] for bit depths which don't have a [`PixelType`].
+    ///
+    /// ```ignore
+    /// struct Average;
+    /// impl DispatchDepth for Average {
+    ///     type Output = f32;
+    ///     fn call<P: PixelType>(self, view: LayerView<P>) -> f32 {
+    ///         let sum: f32 = view.rows().flatten().map(|px| px.to_f32().red).sum();
+    ///         sum / (view.width() * view.height()) as f32
+    ///     }
+    /// }
+    /// let average_red = layer.dispatch_depth(Average)?;
+    /// ```
+    pub fn dispatch_depth<F: DispatchDepth>(&self, f: F) -> Result<F::Output, Error> {
+        match self.bit_depth() {
+            8  => Ok(f.call(self.view::<Pixel8>()?)),
+            16 => Ok(f.call(self.view::<Pixel16>()?)),
+            32 => Ok(f.call(self.view::<PixelF32>()?)),
+            _  => Err(Error::BadCallbackParameter),
+        }
+    }
+
+    /// Call `f` with a mutable view of the pixel type matching the bit depth of the layer.
+    ///
+    /// Returns [`Error::BadCallbackParameter`] for bit depths which don't have a [`PixelType`].
+    pub fn dispatch_depth_mut<F: DispatchDepthMut>(&mut self, f: F) -> Result<F::Output, Error> {
+        match self.bit_depth() {
+            8  => Ok(f.call(self.view_mut::<Pixel8>()?)),
+            16 => Ok(f.call(self.view_mut::<Pixel16>()?)),
+            32 => Ok(f.call(self.view_mut::<PixelF32>()?)),
+            _  => Err(Error::BadCallbackParameter),
+        }
+    }
+
+    /// Typed read-only view of the pixels.
+    ///
+    /// Returns [`Error::BadCallbackParameter`] if the bit depth of the layer doesn't match `P`,
+    /// or if the pixels aren't ARGB, like BGRA or VUYA layers in Premiere. Use [`read_rgba_row()`](Self::read_rgba_row) for those.
+    pub fn view<P: PixelType>(&self) -> Result<LayerView<'_, P>, Error> {
+        self.check_pixel_type::<P>()?;
+        unsafe { LayerView::from_raw_parts(self.data_ptr(), self.row_bytes(), self.width(), self.height()) }
+    }
+
+    /// Typed mutable view of the pixels.
+    ///
+    /// Returns [`Error::BadCallbackParameter`] if the bit depth of the layer doesn't match `P`,
+    /// or if the pixels aren't ARGB, like BGRA or VUYA layers in Premiere. Use [`write_rgba_row()`](Self::write_rgba_row) for those.
+    pub fn view_mut<P: PixelType>(&mut self) -> Result<LayerViewMut<'_, P>, Error> {
+        self.check_pixel_type::<P>()?;
+        unsafe { LayerViewMut::from_raw_parts(self.data_ptr_mut(), self.row_bytes(), self.width(), self.height()) }
+    }
+
+    fn check_pixel_type<P: PixelType>(&self) -> Result<(), Error> {
+        if self.bit_depth() != P::BIT_DEPTH {
+            return Err(Error::BadCallbackParameter);
+        }
+        // The pixel types are ARGB, which is always the case in After Effects. Premiere can use other formats with the same bit depth,
+        // some of which also have a different size, like 4:2:2 formats.
+        if !self.in_data_ptr.is_null() && InData::from_raw(self.in_data_ptr).is_premiere() {
+            match self.pr_pixel_format()? {
+                pr::PixelFormat::Argb4444_8u |
+                pr::PixelFormat::Argb4444_16u |
+                pr::PixelFormat::Argb4444_32f |
+                pr::PixelFormat::Argb4444_32fLinear => {}
+                _ => return Err(Error::BadCallbackParameter),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_row_chunks() {
+    let (width, height, stride) = (3usize, 5usize, 4usize);
+    let mut buffer = vec![PixelF32 { alpha: 0.0, red: 0.0, green: 0.0, blue: 0.0 }; stride * height];
+    let mut view = unsafe { LayerViewMut::<PixelF32>::from_raw_parts(buffer.as_mut_ptr() as *mut u8, (stride * 16) as isize, width, height).unwrap() };
+
+    let chunks: Vec<_> = view.row_chunks_mut(2).collect();
+    assert_eq!(chunks.iter().map(|x| (x.first_row(), x.height())).collect::<Vec<_>>(), [(0, 2), (2, 2), (4, 1)]);
+    for mut chunk in chunks {
+        let first_row = chunk.first_row();
+        for (y, row) in chunk.rows_mut().enumerate() {
+            row.iter_mut().for_each(|px| px.red = (first_row + y) as f32);
+        }
+    }
+    // Padding pixels are untouched
+    assert!(buffer.chunks(stride).enumerate().all(|(y, row)| row[..width].iter().all(|px| px.red == y as f32) && row[width].red == 0.0));
+}
+
+#[test]
+fn test_layer_view() {
+    use std::sync::atomic::{ AtomicU32, Ordering };
+    static PR_PIXEL_FORMAT: AtomicU32 = AtomicU32::new(0);
+
+    // Host suites used by the layers: the AEGP World suite to fill out a layer from a world handle,
+    // and the Premiere pixel format suite
+    unsafe extern "C" fn fill_out_pf_effect_world(world: ae_sys::AEGP_WorldH, layer: *mut ae_sys::PF_EffectWorld) -> ae_sys::A_Err {
+        unsafe { *layer = *(world as *const ae_sys::PF_LayerDef) };
+        0
+    }
+    unsafe extern "C" fn get_pixel_format(_: *mut ae_sys::PF_EffectWorld, format: *mut ae_sys::PrPixelFormat) -> ae_sys::PF_Err {
+        unsafe { *format = PR_PIXEL_FORMAT.load(Ordering::SeqCst) };
+        0
+    }
+    unsafe extern "C" fn acquire_suite(name: *const std::ffi::c_char, _: ae_sys::int32, suite: *mut *const std::ffi::c_void) -> ae_sys::SPErr {
+        let name = unsafe { std::ffi::CStr::from_ptr(name) }.to_bytes_with_nul();
+        if name == ae_sys::kAEGPWorldSuite {
+            let mut world_suite: ae_sys::AEGP_WorldSuite3 = unsafe { std::mem::zeroed() };
+            world_suite.AEGP_FillOutPFEffectWorld = Some(fill_out_pf_effect_world);
+            unsafe { *suite = Box::leak(Box::new(world_suite)) as *const _ as *const _ };
+        } else if name == ae_sys::kPFPixelFormatSuite {
+            let mut pixel_format_suite: ae_sys::PF_PixelFormatSuite1 = unsafe { std::mem::zeroed() };
+            pixel_format_suite.GetPixelFormat = Some(get_pixel_format);
+            unsafe { *suite = Box::leak(Box::new(pixel_format_suite)) as *const _ as *const _ };
+        } else {
+            return 1;
+        }
+        0
+    }
+    let mut basic: ae_sys::SPBasicSuite = unsafe { std::mem::zeroed() };
+    basic.AcquireSuite = Some(acquire_suite);
+    let _pica = PicaBasicSuite::from_sp_basic_suite_raw(&basic);
+
+    // 2x2 pixels with a padding pixel at the end of each row
+    let layer_def = |buffer: &mut [u8], pixel_size: usize, world_flags: WorldFlags| {
+        let mut def: ae_sys::PF_LayerDef = unsafe { std::mem::zeroed() };
+        def.data = buffer.as_mut_ptr() as *mut _;
+        def.rowbytes = (pixel_size * 3) as _;
+        def.width = 2;
+        def.height = 2;
+        def.world_flags = world_flags.bits() as _;
+        def
+    };
+    let null_in_data = std::ptr::null::<ae_sys::PF_InData>();
+
+    let mut buffer8 = vec![0u8; 4 * 3 * 2];
+    let mut def = layer_def(&mut buffer8, 4, WorldFlags::empty());
+    let mut layer = Layer::from_raw(&mut def, null_in_data, None);
+    layer.view_mut::<Pixel8>().unwrap().rows_mut().flatten().for_each(|px| px.red = 255);
+    assert!(layer.view::<Pixel16>().is_err());
+    assert!(layer.view_mut::<PixelF32>().is_err());
+    // The buffer ends with the last pixel
+    assert_eq!(layer.buffer().len(), 4 * 3 + 4 * 2);
+    drop(layer);
+    assert_eq!(buffer8.chunks(4).map(|px| px[1]).collect::<Vec<_>>(), [255, 255, 0, 255, 255, 0]);
+
+    let mut buffer16 = vec![0u8; 8 * 3 * 2];
+    let layer = Layer::from_owned(layer_def(&mut buffer16, 8, WorldFlags::DEEP), null_in_data, |_| {});
+    assert_eq!(layer.view::<Pixel16>().unwrap().rows().count(), 2);
+    assert!(layer.view::<Pixel8>().is_err());
+
+    let mut buffer32 = vec![0u8; 16 * 3 * 2];
+    let world_def = layer_def(&mut buffer32, 16, WorldFlags::RESERVED1);
+    let layer = Layer::from_aegp_world(null_in_data, &world_def as *const _ as ae_sys::AEGP_WorldH).unwrap();
+    assert_eq!(layer.view::<PixelF32>().unwrap().rows().flatten().count(), 4);
+    assert!(layer.view::<Pixel16>().is_err());
+
+    // Premiere layers with the right bit depth but in BGRA order can't be viewed as ARGB
+    let mut in_data: ae_sys::PF_InData = unsafe { std::mem::zeroed() };
+    in_data.appl_id = i32::from_be_bytes(*b"PrMr");
+    let mut def = layer_def(&mut buffer8, 4, WorldFlags::empty());
+    let layer = Layer::from_raw(&mut def, &in_data as *const _, None);
+    PR_PIXEL_FORMAT.store(ae_sys::PrPixelFormat_PrPixelFormat_BGRA_4444_8u, Ordering::SeqCst);
+    assert!(layer.view::<Pixel8>().is_err());
+    PR_PIXEL_FORMAT.store(ae_sys::PrPixelFormat_PrPixelFormat_ARGB_4444_8u, Ordering::SeqCst);
+    assert!(layer.view::<Pixel8>().is_ok());
+}
@@ -5,23 +5,37 @@ use std::{
     marker::PhantomData,
 };
 
+mod audio;      pub use audio::*;
+mod bezier;     pub use bezier::*;
+mod checkout_plan; pub use checkout_plan::*;
 mod command;    pub use command::*;
 mod events;     pub use events::*;
 mod gpu;        pub use gpu::*;
 mod handles;    pub use handles::*;
+mod host_info;  pub use host_info::*;
 mod in_data;    pub use in_data::*;
 mod layer;      pub use layer::*;
+mod layer_view; pub use layer_view::*;
+mod layer_parallel; pub use layer_parallel::*;
+#[cfg(feature = "image")]   mod layer_image;
+#[cfg(feature = "ndarray")] mod layer_ndarray;
 mod out_data;   pub use out_data::*;
 mod parameters; pub use parameters::*;
 mod pixel;      pub use pixel::*;
+mod pixel_layout; pub use pixel_layout::*;
 mod render;     pub use render::*;
+mod sampler;    pub use sampler::*;
 mod effect;     pub use effect::*;
 mod interact_callbacks;    pub use interact_callbacks::*;
 mod util_callbacks;        pub use util_callbacks::*;
 mod external_dependencies; pub use external_dependencies::*;
 
+pub mod blend;
+pub mod ops;
+
 pub mod suites {
     pub(crate) mod adv_item;              pub use adv_item            ::AdvItemSuite               as AdvItem;
+    pub(crate) mod adv_time;              pub use adv_time            ::AdvTimeSuite               as AdvTime;
     pub(crate) mod background_frame;      pub use background_frame    ::BackgroundFrameSuite       as BackgroundFrame;
     pub(crate) mod cache_on_load;         pub use cache_on_load       ::CacheOnLoadSuite           as CacheOnLoad;
     pub(crate) mod channel;               pub use channel             ::ChannelSuite               as Channel;
@@ -39,6 +53,10 @@ pub mod suites {
                                                                           IterateFloatSuite        as IterateFloat };
     pub(crate) mod pixel_data;            pub use pixel_data          ::PixelDataSuite             as PixelData;
     pub(crate) mod pixel_format;          pub use pixel_format        ::PixelFormatSuite           as PixelFormat;
+    pub(crate) mod sampling;              pub use sampling            ::{ Sampling8Suite           as Sampling8,
+                                                                          Sampling16Suite          as Sampling16,
+                                                                          SamplingFloatSuite       as SamplingFloat,
+                                                                          BatchSamplingSuite       as BatchSampling };
     pub(crate) mod source_settings;       pub use source_settings     ::SourceSettingsSuite        as SourceSettings;
     pub(crate) mod transition;            pub use transition          ::TransitionSuite            as Transition;
     pub(crate) mod utility;               pub use utility             ::UtilitySuite               as Utility;
@@ -58,6 +76,8 @@ pub mod suites {
 }
 
 pub use suites::adv_item::Step;
+pub use suites::adv_time::{ TimeDisplayFormat, TimeDisplayPref, CompTiming };
+pub use suites::sampling::{ SamplePixel, SampleFn };
 pub use suites::app::{
     AppColorType,
     AppPersonalTextInfo,
@@ -73,6 +93,13 @@ pub use suites::custom_ui::{
 pub use suites::channel::{
     DataType,
     ChannelType,
+    AuxChannel,
+    Channel,
+    Depth,
+    Normals,
+    MotionVector,
+    ObjectId,
+    BackgroundColor,
 };
 pub use suites::helper::{
     SuiteTool,
@@ -205,14 +232,13 @@ impl Point {
     }
 }
 
-define_struct! {
-    ae_sys::PF_RationalScale,
-    #[derive(Eq)]
-    RationalScale {
-        num: i32,
-        den: u32,
-    }
+/// Compared and hashed by value, see [`Ratio`](crate::Ratio) for the arithmetic.
+#[derive(Debug, Copy, Clone)]
+pub struct RationalScale {
+    pub num: i32,
+    pub den: u32,
 }
+define_struct_conv!(ae_sys::PF_RationalScale, RationalScale { num, den });
 impl RationalScale {
     pub fn inv(&self) -> RationalScale {
         RationalScale { num: self.den as _, den: self.num as _ }
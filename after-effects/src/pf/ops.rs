@@ -0,0 +1,267 @@
+//! Pure-Rust image operations, equivalent to the ones in [`UtilCallbacks`](crate::UtilCallbacks).
+//!
+//! They work on [`LayerView`]s of every bit depth, including 32 bpc, and don't need the host,
+//! so effects using them render the same in After Effects, in Premiere and in tests.
+//!
+//! Filtering is done on premultiplied colors, so transparent pixels don't bleed their color into the result.
+//! Source and destination views must have the same size, unless noted otherwise.
+
+use super::*;
+
+/// Direction of a 1D convolution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Horizontal,
+    Vertical,
+}
+
+/// Premultiplied `[alpha, red, green, blue]` pixels, row by row.
+struct Buffer {
+    width: usize,
+    height: usize,
+    pixels: Vec<[f32; 4]>,
+}
+
+impl Buffer {
+    fn from_view<P: PixelType>(view: &LayerView<P>) -> Self {
+        let pixels = view.rows().flatten().map(|px| {
+            let px = px.to_f32();
+            [px.alpha, px.red * px.alpha, px.green * px.alpha, px.blue * px.alpha]
+        }).collect();
+        Self { width: view.width(), height: view.height(), pixels }
+    }
+
+    fn write_to<P: PixelType>(&self, dst: &mut LayerViewMut<P>) {
+        for (row, src) in dst.rows_mut().zip(self.pixels.chunks_exact(self.width.max(1))) {
+            for (px, &[alpha, red, green, blue]) in row.iter_mut().zip(src) {
+                *px = P::from_f32(if alpha > 0.0 {
+                    PixelF32 { alpha, red: red / alpha, green: green / alpha, blue: blue / alpha }
+                } else {
+                    PixelF32 { alpha: 0.0, red: 0.0, green: 0.0, blue: 0.0 }
+                });
+            }
+        }
+    }
+
+    fn get(&self, x: i64, y: i64, edge_mode: EdgeMode) -> [f32; 4] {
+        match edge_mode.resolve(x, y, self.width, self.height) {
+            Some((x, y)) => self.pixels[y * self.width + x],
+            None => [0.0; 4],
+        }
+    }
+
+    fn convolve_1d(&self, kernel: &[f32], direction: Direction, edge_mode: EdgeMode) -> Self {
+        let center = (kernel.len() / 2) as i64;
+        let mut pixels = Vec::with_capacity(self.pixels.len());
+        for y in 0..self.height as i64 {
+            for x in 0..self.width as i64 {
+                let mut sum = [0.0f32; 4];
+                for (i, &weight) in kernel.iter().enumerate() {
+                    let offset = i as i64 - center;
+                    let px = match direction {
+                        Direction::Horizontal => self.get(x + offset, y, edge_mode),
+                        Direction::Vertical   => self.get(x, y + offset, edge_mode),
+                    };
+                    sum.iter_mut().zip(px).for_each(|(s, c)| *s += c * weight);
+                }
+                pixels.push(sum);
+            }
+        }
+        Self { width: self.width, height: self.height, pixels }
+    }
+}
+
+fn check_same_size<P: PixelType>(src: &LayerView<P>, dst: &LayerViewMut<P>) -> Result<(), Error> {
+    if src.width() != dst.width() || src.height() != dst.height() {
+        return Err(Error::BadCallbackParameter);
+    }
+    Ok(())
+}
+
+/// Multiply the color channels by alpha, in place.
+pub fn premultiply<P: PixelType>(view: &mut LayerViewMut<P>) {
+    view.rows_mut().flatten().for_each(|px| *px = px.premultiply());
+}
+
+/// Divide the color channels by alpha, in place.
+pub fn unpremultiply<P: PixelType>(view: &mut LayerViewMut<P>) {
+    view.rows_mut().flatten().for_each(|px| *px = px.unpremultiply());
+}
+
+/// Normalized 1D gaussian kernel which covers `-radius..=radius` pixels, with a standard deviation of `radius / 3`.
+///
+/// This is the pure-Rust counterpart of [`UtilCallbacks::gaussian_kernel()`](crate::UtilCallbacks::gaussian_kernel).
+pub fn gaussian_kernel(radius: f32) -> Vec<f32> {
+    let size = radius.max(0.0).ceil() as i64;
+    let sigma = (radius / 3.0).max(f32::EPSILON);
+    let kernel: Vec<f32> = (-size..=size).map(|x| (-(x * x) as f32 / (2.0 * sigma * sigma)).exp()).collect();
+    let sum: f32 = kernel.iter().sum();
+    kernel.into_iter().map(|x| x / sum).collect()
+}
+
+/// Convolve `src` with a 1D `kernel` in one direction. The center of the kernel is at `kernel.len() / 2`.
+pub fn convolve_1d<P: PixelType>(src: LayerView<P>, dst: &mut LayerViewMut<P>, kernel: &[f32], direction: Direction, edge_mode: EdgeMode) -> Result<(), Error> {
+    check_same_size(&src, dst)?;
+    if kernel.is_empty() {
+        return Err(Error::BadCallbackParameter);
+    }
+    Buffer::from_view(&src).convolve_1d(kernel, direction, edge_mode).write_to(dst);
+    Ok(())
+}
+
+/// Convolve `src` with a 2D `kernel` of `kernel_width` x `kernel_height` weights, stored row by row.
+///
+/// The center of the kernel is at `(kernel_width / 2, kernel_height / 2)`. The kernel is used as is, so normalize it if needed.
+pub fn convolve<P: PixelType>(src: LayerView<P>, dst: &mut LayerViewMut<P>, kernel: &[f32], kernel_width: usize, kernel_height: usize, edge_mode: EdgeMode) -> Result<(), Error> {
+    check_same_size(&src, dst)?;
+    if kernel_width == 0 || kernel_height == 0 || kernel.len() != kernel_width * kernel_height {
+        return Err(Error::BadCallbackParameter);
+    }
+    let buffer = Buffer::from_view(&src);
+    let (cx, cy) = ((kernel_width / 2) as i64, (kernel_height / 2) as i64);
+    let mut pixels = Vec::with_capacity(buffer.pixels.len());
+    for y in 0..buffer.height as i64 {
+        for x in 0..buffer.width as i64 {
+            let mut sum = [0.0f32; 4];
+            for (i, &weight) in kernel.iter().enumerate() {
+                let (kx, ky) = ((i % kernel_width) as i64, (i / kernel_width) as i64);
+                let px = buffer.get(x + kx - cx, y + ky - cy, edge_mode);
+                sum.iter_mut().zip(px).for_each(|(s, c)| *s += c * weight);
+            }
+            pixels.push(sum);
+        }
+    }
+    Buffer { pixels, ..buffer }.write_to(dst);
+    Ok(())
+}
+
+/// Separable gaussian blur, see [`gaussian_kernel()`] for the meaning of `radius`.
+pub fn gaussian_blur<P: PixelType>(src: LayerView<P>, dst: &mut LayerViewMut<P>, radius: f32, edge_mode: EdgeMode) -> Result<(), Error> {
+    check_same_size(&src, dst)?;
+    let kernel = gaussian_kernel(radius);
+    Buffer::from_view(&src)
+        .convolve_1d(&kernel, Direction::Horizontal, edge_mode)
+        .convolve_1d(&kernel, Direction::Vertical, edge_mode)
+        .write_to(dst);
+    Ok(())
+}
+
+/// Separable box blur, averaging `-radius..=radius` pixels in both directions.
+pub fn box_blur<P: PixelType>(src: LayerView<P>, dst: &mut LayerViewMut<P>, radius: usize, edge_mode: EdgeMode) -> Result<(), Error> {
+    check_same_size(&src, dst)?;
+    let kernel = vec![1.0 / (radius * 2 + 1) as f32; radius * 2 + 1];
+    Buffer::from_view(&src)
+        .convolve_1d(&kernel, Direction::Horizontal, edge_mode)
+        .convolve_1d(&kernel, Direction::Vertical, edge_mode)
+        .write_to(dst);
+    Ok(())
+}
+
+/// Transform `src` into `dst` with an affine matrix, replacing the contents of `dst`. The views may have different sizes.
+///
+/// * `matrix` - maps source coordinates to destination coordinates, like `src2dst_matrix` in [`UtilCallbacks::transform_world()`](crate::UtilCallbacks::transform_world).
+///
+/// Returns [`Error::BadCallbackParameter`] if the matrix isn't invertible.
+pub fn transform_world<P: PixelType>(src: LayerView<P>, dst: &mut LayerViewMut<P>, matrix: &Matrix3, filter: Filter, edge_mode: EdgeMode) -> Result<(), Error> {
+    let inverse = matrix.inverse().ok_or(Error::BadCallbackParameter)?;
+    let sampler = Sampler::new(src).with_filter(filter).with_edge_mode(edge_mode);
+    for (y, row) in dst.rows_mut().enumerate() {
+        for (x, px) in row.iter_mut().enumerate() {
+            let (sx, sy) = inverse.transform_point(x as f64, y as f64);
+            *px = sampler.sample_as(sx as f32, sy as f32);
+        }
+    }
+    Ok(())
+}
+
+/// Alpha weighted mix of two images: `src1` at `ratio` 0.0 and `src2` at `ratio` 1.0. See [`UtilCallbacks::blend()`](crate::UtilCallbacks::blend).
+pub fn blend<P: PixelType>(src1: LayerView<P>, src2: LayerView<P>, ratio: f32, dst: &mut LayerViewMut<P>) -> Result<(), Error> {
+    check_same_size(&src1, dst)?;
+    check_same_size(&src2, dst)?;
+    let (a, b) = (Buffer::from_view(&src1), Buffer::from_view(&src2));
+    let pixels = a.pixels.iter().zip(&b.pixels).map(|(a, b)| std::array::from_fn(|i| a[i] + (b[i] - a[i]) * ratio)).collect();
+    Buffer { pixels, ..a }.write_to(dst);
+    Ok(())
+}
+
+/// Composite `src` onto `dst`, with its top left corner at `dest_x`, `dest_y`. See [`UtilCallbacks::composite_rect()`](crate::UtilCallbacks::composite_rect).
+///
+/// * `opacity` - `0.0..=1.0`
+/// * `transfer_mode` - any mode, see [`blend`](super::blend). [`TransferMode::Dissolve`] uses [`blend::dissolve_noise()`](super::blend::dissolve_noise) with a seed of 0.
+pub fn composite<P: PixelType>(src: LayerView<P>, dst: &mut LayerViewMut<P>, dest_x: i32, dest_y: i32, opacity: f32, transfer_mode: TransferMode) -> Result<(), Error> {
+    for (sy, src_row) in src.rows().enumerate() {
+        let dy = dest_y as i64 + sy as i64;
+        if dy < 0 || dy >= dst.height() as i64 {
+            continue;
+        }
+        let dst_row = dst.row_mut(dy as usize);
+        for (sx, src_px) in src_row.iter().enumerate() {
+            let dx = dest_x as i64 + sx as i64;
+            if dx < 0 || dx >= dst_row.len() as i64 {
+                continue;
+            }
+            let dst_px = &mut dst_row[dx as usize];
+            *dst_px = if transfer_mode == TransferMode::Dissolve {
+                super::blend::dissolve(*src_px, *dst_px, opacity, super::blend::dissolve_noise(dx as i32, dy as i32, 0))
+            } else {
+                super::blend::blend(transfer_mode, *src_px, *dst_px, opacity)
+            };
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_ops() {
+    let clear = PixelF32 { alpha: 0.0, red: 1.0, green: 0.0, blue: 0.0 };
+    let white = PixelF32 { alpha: 1.0, red: 1.0, green: 1.0, blue: 1.0 };
+    let mut src = [clear, white, clear];
+    let mut dst = [clear; 3];
+    let src_view = unsafe { LayerView::<PixelF32>::from_raw_parts(src.as_mut_ptr() as *const u8, 48, 3, 1).unwrap() };
+    let mut dst_view = unsafe { LayerViewMut::<PixelF32>::from_raw_parts(dst.as_mut_ptr() as *mut u8, 48, 3, 1).unwrap() };
+
+    // The red color of transparent pixels doesn't bleed into the blur
+    box_blur(src_view, &mut dst_view, 1, EdgeMode::Clamp).unwrap();
+    assert!((dst_view.pixel(0, 0).alpha - 1.0 / 3.0).abs() < 1e-6 && dst_view.pixel(0, 0).green == 1.0);
+
+    let kernel = gaussian_kernel(3.0);
+    assert_eq!(kernel.len(), 7);
+    assert!((kernel.iter().sum::<f32>() - 1.0).abs() < 1e-6);
+
+    transform_world(src_view, &mut dst_view, &Matrix3::translate(1.0, 0.0), Filter::Nearest, EdgeMode::Transparent).unwrap();
+    assert_eq!(dst_view.rows().flatten().map(|x| x.alpha).collect::<Vec<_>>(), [0.0, 0.0, 1.0]);
+
+    composite(src_view, &mut dst_view, -1, 0, 0.5, TransferMode::InFront).unwrap();
+    assert_eq!(dst_view.pixel(0, 0).alpha, 0.5);
+    assert_eq!(dst_view.pixel(2, 0).alpha, 1.0);
+
+    // The kernel center is at (1, 0), so a weight on the left picks the pixel on the left
+    convolve(src_view, &mut dst_view, &[1.0, 0.0, 0.0], 3, 1, EdgeMode::Transparent).unwrap();
+    assert_eq!(dst_view.rows().flatten().map(|x| x.alpha).collect::<Vec<_>>(), [0.0, 0.0, 1.0]);
+    let px = dst_view.pixel(2, 0);
+    assert_eq!([px.alpha, px.red, px.green, px.blue], [white.alpha, white.red, white.green, white.blue]);
+
+    // Rows above and below are clamped to the only row, so a 3x3 box is a horizontal average
+    convolve(src_view, &mut dst_view, &[1.0 / 9.0; 9], 3, 3, EdgeMode::Clamp).unwrap();
+    assert!(dst_view.rows().flatten().all(|x| (x.alpha - 1.0 / 3.0).abs() < 1e-6 && (x.green - 1.0).abs() < 1e-6));
+    assert!(dst_view.rows().flatten().all(|x| (x.red - 1.0).abs() < 1e-6 && (x.blue - 1.0).abs() < 1e-6));
+
+    assert_eq!(convolve(src_view, &mut dst_view, &[1.0; 4], 3, 1, EdgeMode::Clamp), Err(Error::BadCallbackParameter));
+}
+
+#[test]
+fn test_ops_pixel16() {
+    let px = |alpha, red, green, blue| Pixel16 { alpha, red, green, blue };
+    let channels = |pixels: &[Pixel16]| pixels.iter().map(|x| [x.alpha, x.red, x.green, x.blue]).collect::<Vec<_>>();
+    let mut src = [px(32768, 12345, 0, 32768), px(16384, 1, 20000, 32767), px(0, 0, 0, 0), px(32768, 100, 200, 300)];
+    let mut dst = [px(0, 0, 0, 0); 4];
+    let src_view = unsafe { LayerView::<Pixel16>::from_raw_parts(src.as_mut_ptr() as *const u8, 16, 2, 2).unwrap() };
+    let mut dst_view = unsafe { LayerViewMut::<Pixel16>::from_raw_parts(dst.as_mut_ptr() as *mut u8, 16, 2, 2).unwrap() };
+
+    // Premultiplying into the buffer and back doesn't change 16-bit values
+    box_blur(src_view, &mut dst_view, 0, EdgeMode::Clamp).unwrap();
+    assert_eq!(channels(&dst), channels(&src));
+
+    convolve(src_view, &mut dst_view, &[1.0], 1, 1, EdgeMode::Wrap).unwrap();
+    assert_eq!(channels(&dst), channels(&src));
+}
@@ -8,6 +8,112 @@ pub type Pixel16 = ae_sys::PF_Pixel16;
 pub type PixelF32 = ae_sys::PF_Pixel32;
 pub type PixelF64 = ae_sys::AEGP_ColorVal;
 
+/// A pixel type stored in a [`Layer`](crate::Layer), used by the typed [`LayerView`](crate::LayerView)s.
+///
+/// Implemented for [`Pixel8`], [`Pixel16`] and [`PixelF32`], so pixel math can be written once for all bit depths.
+/// Note that 16-bit channels go from `0` to [`MAX_CHANNEL16`] (32768), not 65535.
+///
+/// See [`Layer::dispatch_depth()`](crate::Layer::dispatch_depth) to call generic code with the bit depth of a layer.
+///
+/// # Safety
+/// Implementors must be plain-old-data with the exact memory layout After Effects uses for layers of [`BIT_DEPTH`](Self::BIT_DEPTH) bits per channel.
+pub unsafe trait PixelType: Copy + Send + Sync + 'static {
+    /// Bits per channel, as returned by [`Layer::bit_depth()`](crate::Layer::bit_depth).
+    const BIT_DEPTH: i16;
+    /// The type of a single channel.
+    type Channel: Copy + PartialOrd + Send + Sync + 'static;
+    /// Value of a fully opaque alpha, or a full intensity color channel.
+    const MAX: Self::Channel;
+    /// Half of [`MAX`](Self::MAX), rounded down.
+    const HALF: Self::Channel;
+
+    /// Convert a channel to `0.0..=1.0`.
+    fn channel_to_f32(channel: Self::Channel) -> f32;
+    /// Convert a channel from `0.0..=1.0`. Integer channels are clamped and rounded to nearest.
+    fn channel_from_f32(value: f32) -> Self::Channel;
+
+    /// Channels in `[alpha, red, green, blue]` order.
+    fn channels(&self) -> [Self::Channel; 4];
+    /// Create a pixel from channels in `[alpha, red, green, blue]` order.
+    fn from_channels(channels: [Self::Channel; 4]) -> Self;
+
+    /// Normalize to `0.0..=1.0` floats.
+    fn to_f32(&self) -> PixelF32 {
+        let [alpha, red, green, blue] = self.channels().map(Self::channel_to_f32);
+        PixelF32 { alpha, red, green, blue }
+    }
+    /// Create from normalized `0.0..=1.0` floats. Integer channels are clamped and rounded to nearest.
+    fn from_f32(p: PixelF32) -> Self {
+        Self::from_channels([p.alpha, p.red, p.green, p.blue].map(Self::channel_from_f32))
+    }
+
+    /// Multiply the color channels by alpha.
+    fn premultiply(&self) -> Self {
+        let p = self.to_f32();
+        Self::from_f32(PixelF32 { alpha: p.alpha, red: p.red * p.alpha, green: p.green * p.alpha, blue: p.blue * p.alpha })
+    }
+    /// Divide the color channels by alpha. Fully transparent pixels become transparent black.
+    fn unpremultiply(&self) -> Self {
+        let p = self.to_f32();
+        if p.alpha <= 0.0 {
+            return Self::from_f32(PixelF32 { alpha: p.alpha, red: 0.0, green: 0.0, blue: 0.0 });
+        }
+        Self::from_f32(PixelF32 { alpha: p.alpha, red: p.red / p.alpha, green: p.green / p.alpha, blue: p.blue / p.alpha })
+    }
+}
+
+macro_rules! impl_integer_pixel {
+    ($pixel:ty, $channel:ty, $bit_depth:expr, $max:expr, $half:expr) => {
+        unsafe impl PixelType for $pixel {
+            const BIT_DEPTH: i16 = $bit_depth;
+            type Channel = $channel;
+            const MAX: $channel = $max as $channel;
+            const HALF: $channel = $half as $channel;
+
+            fn channel_to_f32(channel: $channel) -> f32 {
+                channel as f32 / Self::MAX as f32
+            }
+            fn channel_from_f32(value: f32) -> $channel {
+                (value.clamp(0.0, 1.0) * Self::MAX as f32 + 0.5) as $channel
+            }
+            fn channels(&self) -> [$channel; 4] {
+                [self.alpha, self.red, self.green, self.blue]
+            }
+            fn from_channels([alpha, red, green, blue]: [$channel; 4]) -> Self {
+                Self { alpha, red, green, blue }
+            }
+        }
+    };
+}
+impl_integer_pixel!(Pixel8,  u8,  8,  ae_sys::PF_MAX_CHAN8,  ae_sys::PF_HALF_CHAN8);
+impl_integer_pixel!(Pixel16, u16, 16, ae_sys::PF_MAX_CHAN16, ae_sys::PF_HALF_CHAN16);
+
+unsafe impl PixelType for PixelF32 {
+    const BIT_DEPTH: i16 = 32;
+    type Channel = f32;
+    const MAX: f32 = 1.0;
+    const HALF: f32 = 0.5;
+
+    fn channel_to_f32(channel: f32) -> f32 {
+        channel
+    }
+    fn channel_from_f32(value: f32) -> f32 {
+        value
+    }
+    fn channels(&self) -> [f32; 4] {
+        [self.alpha, self.red, self.green, self.blue]
+    }
+    fn from_channels([alpha, red, green, blue]: [f32; 4]) -> Self {
+        Self { alpha, red, green, blue }
+    }
+    fn to_f32(&self) -> PixelF32 {
+        *self
+    }
+    fn from_f32(p: PixelF32) -> Self {
+        p
+    }
+}
+
 pub use ae_sys::PF_MAX_CHAN8 as MAX_CHANNEL8;
 pub use ae_sys::PF_HALF_CHAN8 as HALF_CHANNEL8;
 pub use ae_sys::PF_MAX_CHAN16 as MAX_CHANNEL16;
@@ -107,3 +213,15 @@ impl<'a> GenericPixelMut<'a> {
         }
     }
 }
+
+#[test]
+fn test_pixel_type() {
+    let p8 = Pixel8 { alpha: 128, red: 255, green: 64, blue: 0 };
+    assert_eq!(Pixel16::from_f32(p8.to_f32()).channels(), pixel8_to_16(p8).channels());
+    assert_eq!(Pixel8::from_f32(pixel8_to_16(p8).to_f32()).channels(), p8.channels());
+
+    let premultiplied = p8.premultiply();
+    assert_eq!(premultiplied.channels(), [128, 128, 32, 0]);
+    assert_eq!(premultiplied.unpremultiply().channels(), [128, 255, 64, 0]);
+    assert_eq!(Pixel16 { alpha: 0, red: 100, green: 100, blue: 100 }.unpremultiply().channels(), [0; 4]);
+}
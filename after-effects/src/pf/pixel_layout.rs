@@ -0,0 +1,465 @@
+use super::*;
+
+// Memory layouts of the Premiere pixel formats, and conversions between them and logical RGBA.
+//
+// All conversions work on plain byte slices, so they can be used on any buffer, not only on a `Layer`.
+// Logical RGBA is represented by `PixelF32`, normalized to 0.0..=1.0 with straight (not premultiplied) alpha.
+// Transfer functions (linear, PQ) are not applied, the values are returned as stored.
+
+/// Order of the channels in memory, for formats with four channels per pixel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChannelOrder {
+    /// Alpha, red, green, blue. The After Effects layout.
+    Argb,
+    /// Blue, green, red, alpha. The native Premiere layout.
+    Bgra,
+    /// V (Cr), U (Cb), Y, alpha.
+    Vuya(YuvMatrix),
+}
+
+/// Meaning of the fourth channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AlphaMode {
+    /// Straight alpha.
+    Straight,
+    /// Color channels are premultiplied by alpha (the `P` formats).
+    Premultiplied,
+    /// The channel is unused and the pixel is opaque (the `X` formats).
+    Ignored,
+}
+
+/// Storage of a single channel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SampleType {
+    /// `0..=255`
+    U8,
+    /// `0..=32768`, same as After Effects' 16 bpc.
+    U16,
+    /// `0.0..=1.0`
+    F32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum YuvMatrix {
+    Bt601,
+    Bt709,
+}
+
+/// Memory layout of a pixel format, used to read and write logical RGBA.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PixelLayout {
+    /// Four channels per pixel, of the same sample type.
+    Packed { order: ChannelOrder, sample: SampleType, alpha: AlphaMode },
+    /// Full range 10-bit RGB, one little-endian `u32` per pixel with blue in bits `0..10`, green in `10..20` and red in `20..30`.
+    Rgb10,
+    /// Full range 12-bit RGB, three little-endian `u16` per pixel in red, green, blue order, with the value in the low 12 bits.
+    Rgb12,
+    /// Video range 10-bit 4:2:2 YUV, 6 pixels packed in 4 little-endian `u32`s.
+    V210(YuvMatrix),
+}
+
+impl PixelLayout {
+    /// The layout of After Effects layers of the given bit depth.
+    pub fn after_effects(bit_depth: i16) -> Option<Self> {
+        let sample = match bit_depth {
+            8  => SampleType::U8,
+            16 => SampleType::U16,
+            32 => SampleType::F32,
+            _  => return None,
+        };
+        Some(Self::Packed { order: ChannelOrder::Argb, sample, alpha: AlphaMode::Straight })
+    }
+
+    /// Number of bytes needed for `width` pixels.
+    pub fn row_size(&self, width: usize) -> usize {
+        match self {
+            Self::Packed { sample: SampleType::U8,  .. } => width * 4,
+            Self::Packed { sample: SampleType::U16, .. } => width * 8,
+            Self::Packed { sample: SampleType::F32, .. } => width * 16,
+            Self::Rgb10 => width * 4,
+            Self::Rgb12 => width * 6,
+            Self::V210(_) => width.div_ceil(6) * 16,
+        }
+    }
+
+    /// Read `dst.len()` pixels from `src` as logical RGBA.
+    ///
+    /// Panics if `src` is smaller than [`row_size()`](Self::row_size) of `dst.len()` pixels.
+    pub fn decode_row(&self, src: &[u8], dst: &mut [PixelF32]) {
+        assert!(src.len() >= self.row_size(dst.len()), "Source row is too short");
+        match *self {
+            Self::Packed { order, sample, alpha } => {
+                for (x, px) in dst.iter_mut().enumerate() {
+                    let [c0, c1, c2, c3] = std::array::from_fn(|i| read_sample(src, sample, x * 4 + i));
+                    let (a, r, g, b) = match order {
+                        ChannelOrder::Argb => (c0, c1, c2, c3),
+                        ChannelOrder::Bgra => (c3, c2, c1, c0),
+                        ChannelOrder::Vuya(matrix) => {
+                            let (y, u, v) = yuv_from_sample(sample, c2, c1, c0);
+                            let (r, g, b) = yuv_to_rgb(matrix, y, u, v);
+                            (c3, r, g, b)
+                        }
+                    };
+                    *px = match alpha {
+                        AlphaMode::Straight      => PixelF32 { alpha: a, red: r, green: g, blue: b },
+                        AlphaMode::Ignored       => PixelF32 { alpha: 1.0, red: r, green: g, blue: b },
+                        AlphaMode::Premultiplied => PixelF32 { alpha: a, red: r, green: g, blue: b }.unpremultiply(),
+                    };
+                }
+            }
+            Self::Rgb10 => {
+                for (x, px) in dst.iter_mut().enumerate() {
+                    let word = read_u32(src, x);
+                    let channel = |shift: u32| ((word >> shift) & 0x3ff) as f32 / 1023.0;
+                    *px = PixelF32 { alpha: 1.0, red: channel(20), green: channel(10), blue: channel(0) };
+                }
+            }
+            Self::Rgb12 => {
+                for (x, px) in dst.iter_mut().enumerate() {
+                    let channel = |i: usize| (read_u16(src, x * 3 + i) & 0xfff) as f32 / 4095.0;
+                    *px = PixelF32 { alpha: 1.0, red: channel(0), green: channel(1), blue: channel(2) };
+                }
+            }
+            Self::V210(matrix) => {
+                let width = dst.len();
+                for (group, pixels) in dst.chunks_mut(6).enumerate() {
+                    let words: [u32; 4] = std::array::from_fn(|i| read_u32(src, group * 4 + i));
+                    let [cb0, y0, cr0, y1, cb2, y2, cr2, y3, cb4, y4, cr4, y5] = unpack_v210(words);
+                    let samples = [(y0, cb0, cr0), (y1, cb0, cr0), (y2, cb2, cr2), (y3, cb2, cr2), (y4, cb4, cr4), (y5, cb4, cr4)];
+                    for (px, (y, u, v)) in pixels.iter_mut().zip(samples).take(width - group * 6) {
+                        let (r, g, b) = yuv_to_rgb(matrix, (y as f32 - 64.0) / 876.0, (u as f32 - 512.0) / 896.0, (v as f32 - 512.0) / 896.0);
+                        *px = PixelF32 { alpha: 1.0, red: r, green: g, blue: b };
+                    }
+                }
+            }
+        }
+    }
+
+    /// Read pixel `x` of the row `src` as logical RGBA, without decoding the pixels before it.
+    ///
+    /// Panics if `src` is smaller than [`row_size()`](Self::row_size) of `x + 1` pixels.
+    pub fn decode_pixel(&self, src: &[u8], x: usize) -> PixelF32 {
+        // V210 pixels share chroma, so decode the whole group of 6 containing `x`
+        let (first, count) = match self {
+            Self::V210(_) => (x / 6 * 6, 6),
+            _ => (x, 1),
+        };
+        let mut pixels = [PixelF32 { alpha: 0.0, red: 0.0, green: 0.0, blue: 0.0 }; 6];
+        self.decode_row(&src[self.row_size(first)..], &mut pixels[..count]);
+        pixels[x - first]
+    }
+
+    /// Write logical RGBA pixels from `src` to `dst` in this layout.
+    ///
+    /// Integer formats are clamped. Panics if `dst` is smaller than [`row_size()`](Self::row_size) of `src.len()` pixels.
+    pub fn encode_row(&self, src: &[PixelF32], dst: &mut [u8]) {
+        assert!(dst.len() >= self.row_size(src.len()), "Destination row is too short");
+        match *self {
+            Self::Packed { order, sample, alpha } => {
+                for (x, px) in src.iter().enumerate() {
+                    let px = match alpha {
+                        AlphaMode::Straight      => *px,
+                        AlphaMode::Ignored       => PixelF32 { alpha: 1.0, ..*px },
+                        AlphaMode::Premultiplied => px.premultiply(),
+                    };
+                    let channels = match order {
+                        ChannelOrder::Argb => [px.alpha, px.red, px.green, px.blue],
+                        ChannelOrder::Bgra => [px.blue, px.green, px.red, px.alpha],
+                        ChannelOrder::Vuya(matrix) => {
+                            let (y, u, v) = rgb_to_yuv(matrix, px.red, px.green, px.blue);
+                            let (y, u, v) = yuv_to_sample(sample, y, u, v);
+                            [v, u, y, px.alpha]
+                        }
+                    };
+                    for (i, value) in channels.into_iter().enumerate() {
+                        write_sample(dst, sample, x * 4 + i, value);
+                    }
+                }
+            }
+            Self::Rgb10 => {
+                for (x, px) in src.iter().enumerate() {
+                    let channel = |value: f32| (value.clamp(0.0, 1.0) * 1023.0).round() as u32;
+                    write_u32(dst, x, (channel(px.red) << 20) | (channel(px.green) << 10) | channel(px.blue));
+                }
+            }
+            Self::Rgb12 => {
+                for (x, px) in src.iter().enumerate() {
+                    for (i, value) in [px.red, px.green, px.blue].into_iter().enumerate() {
+                        write_u16(dst, x * 3 + i, (value.clamp(0.0, 1.0) * 4095.0).round() as u16);
+                    }
+                }
+            }
+            Self::V210(matrix) => {
+                let video = |value: f32, offset: f32, range: f32| (value * range + offset).round().clamp(4.0, 1019.0) as u32;
+                for (group, pixels) in src.chunks(6).enumerate() {
+                    // Pad the last group by repeating the last pixel
+                    let yuv: [(f32, f32, f32); 6] = std::array::from_fn(|i| {
+                        let px = pixels[i.min(pixels.len() - 1)];
+                        rgb_to_yuv(matrix, px.red, px.green, px.blue)
+                    });
+                    let y  = |i: usize| video(yuv[i].0, 64.0, 876.0);
+                    // Chroma is the average of the two pixels sharing it
+                    let cb = |i: usize| video((yuv[i].1 + yuv[i + 1].1) / 2.0, 512.0, 896.0);
+                    let cr = |i: usize| video((yuv[i].2 + yuv[i + 1].2) / 2.0, 512.0, 896.0);
+                    let words = pack_v210([cb(0), y(0), cr(0), y(1), cb(2), y(2), cr(2), y(3), cb(4), y(4), cr(4), y(5)]);
+                    for (i, word) in words.into_iter().enumerate() {
+                        write_u32(dst, group * 4 + i, word);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Convert a row of `width` pixels from this layout to `dst_layout`.
+    pub fn convert_row(&self, src: &[u8], dst_layout: &PixelLayout, dst: &mut [u8], width: usize) {
+        let mut rgba = vec![PixelF32 { alpha: 0.0, red: 0.0, green: 0.0, blue: 0.0 }; width];
+        self.decode_row(src, &mut rgba);
+        dst_layout.encode_row(&rgba, dst);
+    }
+}
+
+impl pr::PixelFormat {
+    /// Memory layout of this format, or `None` if it's not supported by [`PixelLayout`] (planar, compressed and 8-bit 4:2:2 formats).
+    pub fn layout(&self) -> Option<PixelLayout> {
+        use pr::PixelFormat::*;
+        use ChannelOrder as O;
+        use SampleType as S;
+        use AlphaMode as A;
+        let packed = |order, sample, alpha| Some(PixelLayout::Packed { order, sample, alpha });
+        match self {
+            Bgra4444_8u     => packed(O::Bgra, S::U8, A::Straight),
+            Bgrx4444_8u     => packed(O::Bgra, S::U8, A::Ignored),
+            Bgrp4444_8u     => packed(O::Bgra, S::U8, A::Premultiplied),
+            Argb4444_8u     => packed(O::Argb, S::U8, A::Straight),
+            Xrgb4444_8u     => packed(O::Argb, S::U8, A::Ignored),
+            Prgb4444_8u     => packed(O::Argb, S::U8, A::Premultiplied),
+            Vuya4444_8u     => packed(O::Vuya(YuvMatrix::Bt601), S::U8, A::Straight),
+            Vuya4444_8u709  => packed(O::Vuya(YuvMatrix::Bt709), S::U8, A::Straight),
+            Vuyx4444_8u     => packed(O::Vuya(YuvMatrix::Bt601), S::U8, A::Ignored),
+            Vuyx4444_8u709  => packed(O::Vuya(YuvMatrix::Bt709), S::U8, A::Ignored),
+            Vuyp4444_8u     => packed(O::Vuya(YuvMatrix::Bt601), S::U8, A::Premultiplied),
+            Vuyp4444_8u709  => packed(O::Vuya(YuvMatrix::Bt709), S::U8, A::Premultiplied),
+
+            Bgra4444_16u    => packed(O::Bgra, S::U16, A::Straight),
+            Bgrx4444_16u    => packed(O::Bgra, S::U16, A::Ignored),
+            Bgrp4444_16u    => packed(O::Bgra, S::U16, A::Premultiplied),
+            Argb4444_16u    => packed(O::Argb, S::U16, A::Straight),
+            Xrgb4444_16u    => packed(O::Argb, S::U16, A::Ignored),
+            Prgb4444_16u    => packed(O::Argb, S::U16, A::Premultiplied),
+            Vuya4444_16u    => packed(O::Vuya(YuvMatrix::Bt601), S::U16, A::Straight),
+
+            Bgra4444_32f | Bgra4444_32fLinear => packed(O::Bgra, S::F32, A::Straight),
+            Bgrx4444_32f | Bgrx4444_32fLinear => packed(O::Bgra, S::F32, A::Ignored),
+            Bgrp4444_32f | Bgrp4444_32fLinear => packed(O::Bgra, S::F32, A::Premultiplied),
+            Argb4444_32f | Argb4444_32fLinear => packed(O::Argb, S::F32, A::Straight),
+            Xrgb4444_32f | Xrgb4444_32fLinear => packed(O::Argb, S::F32, A::Ignored),
+            Prgb4444_32f | Prgb4444_32fLinear => packed(O::Argb, S::F32, A::Premultiplied),
+            Vuya4444_32f    => packed(O::Vuya(YuvMatrix::Bt601), S::F32, A::Straight),
+            Vuya4444_32f709 => packed(O::Vuya(YuvMatrix::Bt709), S::F32, A::Straight),
+            Vuyx4444_32f    => packed(O::Vuya(YuvMatrix::Bt601), S::F32, A::Ignored),
+            Vuyx4444_32f709 => packed(O::Vuya(YuvMatrix::Bt709), S::F32, A::Ignored),
+            Vuyp4444_32f    => packed(O::Vuya(YuvMatrix::Bt601), S::F32, A::Premultiplied),
+            Vuyp4444_32f709 => packed(O::Vuya(YuvMatrix::Bt709), S::F32, A::Premultiplied),
+
+            Rgb444_10u => Some(PixelLayout::Rgb10),
+            Rgb444_12uPq709 | Rgb444_12uPqP3 | Rgb444_12uPq2020 => Some(PixelLayout::Rgb12),
+            V210422_10u601 => Some(PixelLayout::V210(YuvMatrix::Bt601)),
+            V210422_10u709 => Some(PixelLayout::V210(YuvMatrix::Bt709)),
+            _ => None,
+        }
+    }
+}
+
+impl YuvMatrix {
+    fn coefficients(&self) -> (f32, f32) {
+        match self {
+            Self::Bt601 => (0.299, 0.114),
+            Self::Bt709 => (0.2126, 0.0722),
+        }
+    }
+}
+
+/// Convert full range YUV (`y` in `0.0..=1.0`, `u` and `v` in `-0.5..=0.5`) to RGB.
+pub fn yuv_to_rgb(matrix: YuvMatrix, y: f32, u: f32, v: f32) -> (f32, f32, f32) {
+    let (kr, kb) = matrix.coefficients();
+    let r = y + 2.0 * (1.0 - kr) * v;
+    let b = y + 2.0 * (1.0 - kb) * u;
+    let g = (y - kr * r - kb * b) / (1.0 - kr - kb);
+    (r, g, b)
+}
+
+/// Convert RGB to full range YUV (`y` in `0.0..=1.0`, `u` and `v` in `-0.5..=0.5`).
+pub fn rgb_to_yuv(matrix: YuvMatrix, r: f32, g: f32, b: f32) -> (f32, f32, f32) {
+    let (kr, kb) = matrix.coefficients();
+    let y = kr * r + (1.0 - kr - kb) * g + kb * b;
+    let u = (b - y) / (2.0 * (1.0 - kb));
+    let v = (r - y) / (2.0 * (1.0 - kr));
+    (y, u, v)
+}
+
+// Integer VUYA formats are video range: Y in 16..=235 and UV in 16..=240 centered at 128, scaled for 16-bit.
+// Float VUYA formats are full range.
+fn yuv_from_sample(sample: SampleType, y: f32, u: f32, v: f32) -> (f32, f32, f32) {
+    match sample {
+        SampleType::F32 => (y, u, v),
+        // Samples are already normalized to 0..=1, so scale the video range to the 8-bit range
+        _ => ((y * 255.0 - 16.0) / 219.0, (u * 255.0 - 128.0) / 224.0, (v * 255.0 - 128.0) / 224.0),
+    }
+}
+fn yuv_to_sample(sample: SampleType, y: f32, u: f32, v: f32) -> (f32, f32, f32) {
+    match sample {
+        SampleType::F32 => (y, u, v),
+        _ => ((y * 219.0 + 16.0) / 255.0, (u * 224.0 + 128.0) / 255.0, (v * 224.0 + 128.0) / 255.0),
+    }
+}
+
+fn read_sample(buf: &[u8], sample: SampleType, index: usize) -> f32 {
+    match sample {
+        SampleType::U8  => Pixel8::channel_to_f32(buf[index]),
+        SampleType::U16 => Pixel16::channel_to_f32(read_u16(buf, index)),
+        SampleType::F32 => f32::from_le_bytes(buf[index * 4..index * 4 + 4].try_into().unwrap()),
+    }
+}
+fn write_sample(buf: &mut [u8], sample: SampleType, index: usize, value: f32) {
+    match sample {
+        SampleType::U8  => buf[index] = Pixel8::channel_from_f32(value),
+        SampleType::U16 => write_u16(buf, index, Pixel16::channel_from_f32(value)),
+        SampleType::F32 => buf[index * 4..index * 4 + 4].copy_from_slice(&value.to_le_bytes()),
+    }
+}
+
+fn read_u16(buf: &[u8], index: usize) -> u16 {
+    u16::from_le_bytes([buf[index * 2], buf[index * 2 + 1]])
+}
+fn write_u16(buf: &mut [u8], index: usize, value: u16) {
+    buf[index * 2..index * 2 + 2].copy_from_slice(&value.to_le_bytes());
+}
+fn read_u32(buf: &[u8], index: usize) -> u32 {
+    u32::from_le_bytes(buf[index * 4..index * 4 + 4].try_into().unwrap())
+}
+fn write_u32(buf: &mut [u8], index: usize, value: u32) {
+    buf[index * 4..index * 4 + 4].copy_from_slice(&value.to_le_bytes());
+}
+
+/// Unpack the 12 components of a V210 group, in memory order: `Cb0 Y0 Cr0 Y1 Cb2 Y2 Cr2 Y3 Cb4 Y4 Cr4 Y5`.
+pub fn unpack_v210(words: [u32; 4]) -> [u16; 12] {
+    std::array::from_fn(|i| ((words[i / 3] >> ((i % 3) * 10)) & 0x3ff) as u16)
+}
+
+/// Pack 12 10-bit components into a V210 group. Inverse of [`unpack_v210()`].
+pub fn pack_v210(components: [u32; 12]) -> [u32; 4] {
+    std::array::from_fn(|i| (components[i * 3] & 0x3ff) | ((components[i * 3 + 1] & 0x3ff) << 10) | ((components[i * 3 + 2] & 0x3ff) << 20))
+}
+
+impl Layer {
+    /// Memory layout of the pixels.
+    ///
+    /// In Premiere this is based on [`pr_pixel_format()`](Self::pr_pixel_format), which may not be ARGB,
+    /// so use this (or [`read_rgba_row()`](Self::read_rgba_row)) instead of [`as_pixel8()`](Self::as_pixel8) and friends when rendering in Premiere.
+    /// Returns [`Error::BadCallbackParameter`] if the format isn't supported by [`PixelLayout`].
+    pub fn pixel_layout(&self) -> Result<PixelLayout, Error> {
+        if !self.in_data_ptr.is_null() && InData::from_raw(self.in_data_ptr).is_premiere() {
+            if let Ok(format) = self.pr_pixel_format() {
+                return format.layout().ok_or(Error::BadCallbackParameter);
+            }
+        }
+        PixelLayout::after_effects(self.bit_depth()).ok_or(Error::BadCallbackParameter)
+    }
+
+    fn row_bytes_slice(&self, layout: &PixelLayout, y: usize) -> &[u8] {
+        assert!(y < self.height(), "Row {y} is outside of the layer with height {}", self.height());
+        unsafe { std::slice::from_raw_parts(self.data_ptr().offset(y as isize * self.row_bytes()), layout.row_size(self.width())) }
+    }
+
+    /// Read row `y` as logical RGBA. `dst` must hold at least [`width()`](Self::width) pixels.
+    pub fn read_rgba_row(&self, y: usize, dst: &mut [PixelF32]) -> Result<(), Error> {
+        let layout = self.pixel_layout()?;
+        let width = self.width();
+        if dst.len() < width {
+            return Err(Error::BadCallbackParameter);
+        }
+        layout.decode_row(self.row_bytes_slice(&layout, y), &mut dst[..width]);
+        Ok(())
+    }
+
+    /// Write logical RGBA to row `y`. `src` must hold at least [`width()`](Self::width) pixels.
+    pub fn write_rgba_row(&mut self, y: usize, src: &[PixelF32]) -> Result<(), Error> {
+        let layout = self.pixel_layout()?;
+        let width = self.width();
+        if src.len() < width {
+            return Err(Error::BadCallbackParameter);
+        }
+        assert!(y < self.height(), "Row {y} is outside of the layer with height {}", self.height());
+        let row = unsafe { std::slice::from_raw_parts_mut(self.data_ptr_mut().offset(y as isize * self.row_bytes()), layout.row_size(width)) };
+        layout.encode_row(&src[..width], row);
+        Ok(())
+    }
+
+    /// Read the pixel at `x`, `y` as logical RGBA.
+    pub fn rgba_pixel(&self, x: usize, y: usize) -> Result<PixelF32, Error> {
+        assert!(x < self.width(), "Column {x} is outside of the layer with width {}", self.width());
+        let layout = self.pixel_layout()?;
+        Ok(layout.decode_pixel(self.row_bytes_slice(&layout, y), x))
+    }
+}
+
+#[test]
+fn test_pixel_layout() {
+    let close = |a: PixelF32, b: PixelF32, eps: f32| a.channels().iter().zip(b.channels()).all(|(a, b)| (a - b).abs() <= eps);
+    let pixels = [
+        PixelF32 { alpha: 1.0, red: 1.0, green: 0.0, blue: 0.0 },
+        PixelF32 { alpha: 0.5, red: 0.2, green: 0.6, blue: 0.9 },
+    ];
+
+    // BGRA 8u is stored as B, G, R, A
+    let bgra = pr::PixelFormat::Bgra4444_8u.layout().unwrap();
+    let mut bytes = vec![0u8; bgra.row_size(2)];
+    bgra.encode_row(&pixels, &mut bytes);
+    assert_eq!(bytes[..4], [0, 0, 255, 255]);
+
+    // BGRA -> ARGB swaps the channels
+    let argb = PixelLayout::after_effects(8).unwrap();
+    let mut argb_bytes = vec![0u8; argb.row_size(2)];
+    bgra.convert_row(&bytes, &argb, &mut argb_bytes, 2);
+    assert_eq!(argb_bytes[..4], [255, 255, 0, 0]);
+
+    // YUV round trips
+    for format in [pr::PixelFormat::Vuya4444_8u, pr::PixelFormat::Vuya4444_32f709, pr::PixelFormat::Bgrp4444_16u] {
+        let layout = format.layout().unwrap();
+        let mut bytes = vec![0u8; layout.row_size(2)];
+        let mut decoded = pixels;
+        layout.encode_row(&pixels, &mut bytes);
+        layout.decode_row(&bytes, &mut decoded);
+        assert!(pixels.iter().zip(decoded).all(|(a, b)| close(*a, b, 0.02)), "{format:?}: {decoded:?}");
+    }
+
+    // V210 of a flat color
+    let v210 = PixelLayout::V210(YuvMatrix::Bt709);
+    let gray = [PixelF32 { alpha: 1.0, red: 0.5, green: 0.5, blue: 0.5 }; 7];
+    let mut bytes = vec![0u8; v210.row_size(7)];
+    assert_eq!(bytes.len(), 32);
+    v210.encode_row(&gray, &mut bytes);
+    assert_eq!(unpack_v210(std::array::from_fn(|i| read_u32(&bytes, i)))[..2], [512, 502]);
+    let mut decoded = gray;
+    v210.decode_row(&bytes, &mut decoded);
+    assert!(gray.iter().zip(decoded).all(|(a, b)| close(*a, b, 0.002)));
+
+    // Single pixels decode the same as whole rows, including in the last, partial V210 group
+    assert!((0..7).all(|x| close(v210.decode_pixel(&bytes, x), decoded[x], 0.0)));
+    let bgra16 = pr::PixelFormat::Bgra4444_16u.layout().unwrap();
+    let mut bytes = vec![0u8; bgra16.row_size(2)];
+    bgra16.encode_row(&pixels, &mut bytes);
+    bgra16.decode_row(&bytes, &mut decoded[..2]);
+    assert!((0..2).all(|x| close(bgra16.decode_pixel(&bytes, x), decoded[x], 0.0)));
+
+    // Float samples are little-endian, like the integer ones
+    let argb32 = PixelLayout::after_effects(32).unwrap();
+    let mut bytes = vec![0u8; argb32.row_size(1)];
+    argb32.encode_row(&pixels[1..], &mut bytes);
+    assert_eq!(bytes[..4], 0.5f32.to_le_bytes());
+
+    // 10-bit RGB
+    let mut bytes = [0u8; 4];
+    PixelLayout::Rgb10.encode_row(&pixels[..1], &mut bytes);
+    assert_eq!(u32::from_le_bytes(bytes), 1023 << 20);
+}
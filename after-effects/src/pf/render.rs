@@ -202,19 +202,20 @@ impl SmartRenderExtra {
         }
     }
     pub fn pre_render_data<T: Any>(&self) -> Option<&T> {
-        assert!(!self.as_ref().input.is_null());
-        if unsafe { (*(*self.ptr).input).pre_render_data.is_null() } {
-            return None;
-        }
-        let data = unsafe {
-            Box::<Box<dyn Any>>::from_raw((*(*self.ptr).input).pre_render_data as *mut _)
-        };
-        let data = Box::<Box<dyn Any>>::leak(data);
-        match data.downcast_ref::<T>() {
+        match self.pre_render_data_any()?.downcast_ref::<T>() {
             Some(data) => Some(data),
             None => panic!("Invalid type for pre_render_data"),
         }
     }
+    /// Pre-render data of any type, for callers which handle a type mismatch themselves.
+    pub(crate) fn pre_render_data_any(&self) -> Option<&dyn Any> {
+        assert!(!self.as_ref().input.is_null());
+        let data = unsafe { (*(*self.ptr).input).pre_render_data as *const Box<dyn Any> };
+        if data.is_null() {
+            return None;
+        }
+        Some(unsafe { &**data })
+    }
     pub fn pre_render_data_mut<T: Any>(&mut self) -> Option<&mut T> {
         assert!(!self.as_ref().input.is_null());
         if unsafe { (*(*self.ptr).input).pre_render_data.is_null() } {
@@ -0,0 +1,139 @@
+use crate::*;
+use ae_sys::*;
+use std::ffi::CStr;
+
+define_suite!(
+    /// Formats times the way the user chose in the project settings, and counts the frames of a time range.
+    ///
+    /// The composition frame rate, frame duration, and shutter angle and phase of the effect's composition are returned by [`comp_timing()`](Self::comp_timing).
+    AdvTimeSuite,
+    PF_AdvTimeSuite4,
+    kPFAdvTimeSuite,
+    kPFAdvTimeSuiteVersion4
+);
+
+fn time_from_buf(buf: &[A_char]) -> Result<String, Error> {
+    let buf: &[u8] = unsafe { std::slice::from_raw_parts(buf.as_ptr() as *const u8, buf.len()) };
+    Ok(CStr::from_bytes_until_nul(buf).map_err(|_| Error::InvalidParms)?.to_string_lossy().into_owned())
+}
+
+impl AdvTimeSuite {
+    /// Acquire this suite from the host. Returns error if the suite is not available.
+    /// Suite is released on drop.
+    pub fn new() -> Result<Self, Error> {
+        crate::Suite::new()
+    }
+
+    /// Formats `time` as displayed in the active item, for example `0:00:01:12`.
+    ///
+    /// If `duration` is true, the time is formatted as a duration instead of a point in time.
+    pub fn format_time_active_item(&self, time: Time, duration: bool) -> Result<String, Error> {
+        let mut buf = [0 as A_char; PF_MAX_TIME_LEN as usize + 1];
+        call_suite_fn!(self, PF_FormatTimeActiveItem, time.value, time.scale, duration as _, buf.as_mut_ptr())?;
+        time_from_buf(&buf)
+    }
+
+    /// Formats `time` as displayed in the composition of the effect.
+    pub fn format_time(&self, in_data: impl AsPtr<*mut PF_InData>, world: impl AsPtr<*mut PF_EffectWorld>, time: Time, duration: bool) -> Result<String, Error> {
+        let mut buf = [0 as A_char; PF_MAX_TIME_LEN as usize + 1];
+        call_suite_fn!(self, PF_FormatTime, in_data.as_ptr(), world.as_ptr(), time.value, time.scale, duration as _, buf.as_mut_ptr())?;
+        time_from_buf(&buf)
+    }
+
+    /// Like [`format_time()`](Self::format_time), but `comp_time` selects whether `time` is in composition time or in layer time.
+    pub fn format_time_plus(&self, in_data: impl AsPtr<*mut PF_InData>, world: impl AsPtr<*mut PF_EffectWorld>, time: Time, comp_time: bool, duration: bool) -> Result<String, Error> {
+        let mut buf = [0 as A_char; PF_MAX_TIME_LEN as usize + 1];
+        call_suite_fn!(self, PF_FormatTimePlus, in_data.as_ptr(), world.as_ptr(), time.value, time.scale, comp_time as _, duration as _, buf.as_mut_ptr())?;
+        time_from_buf(&buf)
+    }
+
+    /// Returns the time display preferences of the project, and the starting frame number.
+    pub fn time_display_pref(&self) -> Result<(TimeDisplayPref, i32), Error> {
+        let (pref, starting_frame) = call_suite_fn_double!(self, PF_GetTimeDisplayPref -> PF_TimeDisplayPrefVersion3, A_long)?;
+        Ok((pref.into(), starting_frame))
+    }
+
+    /// Returns the number of frames between `start` and the time step `time_step` after it.
+    ///
+    /// If `include_partial_frame` is true, a partial frame at the end is counted.
+    ///
+    /// There's no floating point version of this in the SDK. For a fractional count, divide `time_step` by the frame duration: `f64::from(time_step / frame_duration)`.
+    pub fn time_count_frames(&self, start: Time, time_step: Time, include_partial_frame: bool) -> Result<i32, Error> {
+        let start: A_Time = start.into();
+        let time_step: A_Time = time_step.into();
+        call_suite_fn_single!(self, PF_TimeCountFrames -> A_long, &start, &time_step, include_partial_frame as _)
+    }
+
+    /// Returns the frame duration, frame rate, and shutter angle and phase of the composition containing the effect's layer.
+    ///
+    /// The composition is found through [`aegp::suites::PFInterface`], so this is only available in After Effects.
+    pub fn comp_timing(&self, in_data: &InData) -> Result<CompTiming, Error> {
+        let layer = aegp::suites::PFInterface::new()?.effect_layer(in_data.effect_ref())?;
+        let comp = aegp::suites::Layer::new()?.layer_parent_comp(layer)?;
+        let comp_suite = aegp::suites::Comp::new()?;
+
+        let frame_duration = comp_suite.comp_frame_duration(comp)?;
+        if frame_duration.value <= 0 || frame_duration.scale == 0 {
+            return Err(Error::InvalidParms);
+        }
+        let (shutter_angle, shutter_phase) = comp_suite.comp_shutter_angle_phase(comp)?;
+        Ok(CompTiming {
+            frame_duration,
+            frame_rate: Ratio::new(frame_duration.value, frame_duration.scale).recip(),
+            shutter_angle,
+            shutter_phase,
+        })
+    }
+}
+
+// ――――――――――――――――――――――――――――――――――――――― Types ――――――――――――――――――――――――――――――――――――――――
+
+define_enum! {
+    ae_sys::A_char,
+    TimeDisplayFormat {
+        Timecode   = ae_sys::PF_TimeDisplayFormatTimecode,
+        Frames     = ae_sys::PF_TimeDisplayFormatFrames,
+        FeetFrames = ae_sys::PF_TimeDisplayFormatFeetFrames,
+    }
+}
+
+/// The time display preferences of the project.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeDisplayPref {
+    pub display_mode: TimeDisplayFormat,
+    /// The frame rate used for timecode display.
+    pub framemax: i32,
+    pub frames_per_foot: i32,
+    /// The frame number of the first frame, `0` or `1`.
+    pub frames_start: i32,
+    /// Show 30 fps timecode as non-drop-frame.
+    pub non_drop_30: bool,
+    pub honor_source_timecode: bool,
+    pub use_feet_frames: bool,
+}
+
+/// Timing of a composition, returned by [`AdvTimeSuite::comp_timing()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompTiming {
+    pub frame_duration: Time,
+    /// Frames per second, the reciprocal of `frame_duration`.
+    pub frame_rate: Ratio,
+    /// Shutter angle in degrees.
+    pub shutter_angle: Ratio,
+    /// Shutter phase in degrees.
+    pub shutter_phase: Ratio,
+}
+
+impl From<PF_TimeDisplayPrefVersion3> for TimeDisplayPref {
+    fn from(pref: PF_TimeDisplayPrefVersion3) -> Self {
+        Self {
+            display_mode:          pref.display_mode.into(),
+            framemax:              pref.framemax,
+            frames_per_foot:       pref.frames_per_foot,
+            frames_start:          pref.frames_start as _,
+            non_drop_30:           pref.nondrop30B != 0,
+            honor_source_timecode: pref.honor_source_timecodeB != 0,
+            use_feet_frames:       pref.use_feet_framesB != 0,
+        }
+    }
+}
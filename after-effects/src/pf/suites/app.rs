@@ -20,7 +20,7 @@ define_suite!(
     kPFAppSuiteVersion6
 );
 
-fn str_from_c(slice: &[i8]) -> Result<String, Error> {
+fn str_from_c(slice: &[std::ffi::c_char]) -> Result<String, Error> {
     let slice: &[u8] = unsafe { std::slice::from_raw_parts(slice.as_ptr() as *const u8, slice.len()) };
     Ok(CStr::from_bytes_until_nul(slice).map_err(|_| Error::InvalidParms)?.to_string_lossy().into_owned())
 }
@@ -4,6 +4,8 @@ use ae_sys::*;
 
 define_suite!(
     /// Some file types contain more than just pixel data; use [`ChannelSuite`] to determine whether such information is present,
+    ///
+    /// Re-exported as `pf::suites::Channel`. For typed, automatically checked in access to a channel use [`Channel`] (`pf::Channel`) instead.
     ChannelSuite,
     PF_ChannelSuite1,
     kPFChannelSuite1,
@@ -229,3 +231,133 @@ impl ChannelChunk {
         }
     }
 }
+
+/// An auxiliary channel which can be checked out with [`Channel`], with the type of its pixels.
+pub trait AuxChannel {
+    const CHANNEL_TYPE: ChannelType;
+    /// Data type of each plane. The host converts the channel to it on checkout.
+    const DATA_TYPE: DataType;
+    /// Number of planes in a pixel.
+    const DIMENSION: i32;
+    /// One pixel: [`DIMENSION`](Self::DIMENSION) planes of [`DATA_TYPE`](Self::DATA_TYPE).
+    type Pixel: Copy;
+}
+
+macro_rules! define_aux_channel {
+    ($(#[$attr:meta])* $name:ident, $channel_type:ident, $data_type:ident, $dimension:literal, $pixel:ty) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy)]
+        pub struct $name;
+        impl AuxChannel for $name {
+            const CHANNEL_TYPE: ChannelType = ChannelType::$channel_type;
+            const DATA_TYPE: DataType = DataType::$data_type;
+            const DIMENSION: i32 = $dimension;
+            type Pixel = $pixel;
+        }
+    };
+}
+
+define_aux_channel!(/// Distance from the camera.
+                    Depth,           Depth,           Float, 1, f32);
+define_aux_channel!(/// Surface normals, `[x, y, z]`.
+                    Normals,         Normals,         Float, 3, [f32; 3]);
+define_aux_channel!(/// Screen space motion, `[x, y]` in pixels.
+                    MotionVector,    MotionVector,    Float, 2, [f32; 2]);
+define_aux_channel!(/// ID of the object rendered in each pixel.
+                    ObjectId,        ObjectID,        Long,  1, i32);
+define_aux_channel!(/// Color of the background behind the objects, `[red, green, blue]`.
+                    BackgroundColor, BackgroundColor, Float, 3, [f32; 3]);
+
+/// An auxiliary channel of a layer parameter, checked out with the pixel type of `C`.
+///
+/// The channel is checked in when this is dropped.
+///
+/// Not to be confused with `pf::suites::Channel`, which is the raw [`ChannelSuite`] used underneath.
+/// This type is re-exported as `pf::Channel`.
+///
+/// ```ignore
+/// if let Some(depth) = ae::Channel::<ae::Depth>::checkout(&in_data, 0)? {
+///     let z = depth.pixel(x, y);
+/// }
+/// ```
+pub struct Channel<C: AuxChannel> {
+    suite: ChannelSuite,
+    effect_ref: PF_ProgPtr,
+    chunk: ChannelChunk,
+    _marker: std::marker::PhantomData<C>,
+}
+
+impl<C: AuxChannel> Channel<C> {
+    /// Check out the channel of the layer parameter `param_index` at the current time.
+    ///
+    /// Returns `None` if the layer doesn't have this channel.
+    pub fn checkout(in_data: &InData, param_index: i32) -> Result<Option<Self>, Error> {
+        Self::checkout_at(in_data, param_index, in_data.current_time(), in_data.time_step(), in_data.time_scale())
+    }
+
+    /// Check out the channel of the layer parameter `param_index` at `what_time`.
+    ///
+    /// Returns [`Error::InvalidParms`] if the host returns a channel with a different number of planes than [`AuxChannel::DIMENSION`].
+    pub fn checkout_at(in_data: &InData, param_index: i32, what_time: i32, duration: i32, time_scale: u32) -> Result<Option<Self>, Error> {
+        let suite = ChannelSuite::new()?;
+        let effect_ref = in_data.effect_ref().as_ptr();
+        let Some((channel_ref, desc)) = suite.layer_channel_typed_ref_and_desc(effect_ref, param_index, C::CHANNEL_TYPE)? else {
+            return Ok(None);
+        };
+        if desc.dimension != C::DIMENSION {
+            return Err(Error::InvalidParms);
+        }
+        let chunk = suite.checkout_layer_channel(effect_ref, &channel_ref, what_time, duration, time_scale, C::DATA_TYPE)?;
+        let channel = Self { suite, effect_ref, chunk, _marker: std::marker::PhantomData };
+
+        let chunk = &channel.chunk;
+        let width = chunk.widthL.max(0) as usize;
+        if chunk.dimensionL != C::DIMENSION || chunk.data_type != PF_DataType::from(C::DATA_TYPE) {
+            return Err(Error::InvalidParms);
+        }
+        if chunk.heightL > 0 && (chunk.dataPV.is_null() || (chunk.row_bytesL.max(0) as usize) < width * std::mem::size_of::<C::Pixel>()) {
+            return Err(Error::BadCallbackParameter);
+        }
+        if !(chunk.dataPV as usize).is_multiple_of(std::mem::align_of::<C::Pixel>()) || !(chunk.row_bytesL as usize).is_multiple_of(std::mem::align_of::<C::Pixel>()) {
+            return Err(Error::BadCallbackParameter);
+        }
+        Ok(Some(channel))
+    }
+
+    pub fn width(&self) -> usize {
+        self.chunk.widthL.max(0) as usize
+    }
+    pub fn height(&self) -> usize {
+        self.chunk.heightL.max(0) as usize
+    }
+    pub fn row_bytes(&self) -> isize {
+        self.chunk.row_bytesL as isize
+    }
+
+    /// Pixels of row `y`. Panics if `y` is out of bounds.
+    pub fn row(&self, y: usize) -> &[C::Pixel] {
+        assert!(y < self.height(), "Row {y} is outside of the channel with height {}", self.height());
+        if self.width() == 0 {
+            return &[];
+        }
+        unsafe { std::slice::from_raw_parts((self.chunk.dataPV as *const u8).offset(y as isize * self.row_bytes()) as *const C::Pixel, self.width()) }
+    }
+
+    /// Pixel at `x`, `y`. Panics if the coordinate is out of bounds.
+    pub fn pixel(&self, x: usize, y: usize) -> C::Pixel {
+        self.row(y)[x]
+    }
+
+    /// Iterator over all rows, from top to bottom.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[C::Pixel]> + ExactSizeIterator + '_ {
+        (0..self.height()).map(move |y| self.row(y))
+    }
+}
+
+impl<C: AuxChannel> Drop for Channel<C> {
+    fn drop(&mut self) {
+        if let Err(e) = self.suite.checkin_layer_channel(self.effect_ref, &self.chunk.channel_ref, &self.chunk) {
+            log::error!("Failed to check in channel {:?}: {e:?}", C::CHANNEL_TYPE);
+        }
+    }
+}
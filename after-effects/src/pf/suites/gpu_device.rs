@@ -12,6 +12,7 @@ impl GPUDeviceSuite {
     /// Acquire this suite from the host. Returns error if the suite is not available.
     /// Suite is released on drop.
     pub fn new() -> Result<Self, Error> {
+        HostInfo::warn_if_unsupported(HostCapability::GpuFramework, "PF_GPUDeviceSuite1");
         crate::Suite::new()
     }
 
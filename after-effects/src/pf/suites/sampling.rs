@@ -0,0 +1,126 @@
+use crate::*;
+use ae_sys::{ PF_ProgPtr, PF_Fixed, PF_SampPB, PF_Err };
+
+define_suite!(
+    /// Nearest neighbor, subpixel and area sampling of 8-bit layers. See [`Sampling`] for the details of each function.
+    Sampling8Suite,
+    PF_Sampling8Suite1,
+    kPFSampling8Suite,
+    kPFSampling8SuiteVersion1
+);
+
+define_suite!(
+    /// Nearest neighbor, subpixel and area sampling of 16-bit layers. See [`Sampling`] for the details of each function.
+    Sampling16Suite,
+    PF_Sampling16Suite1,
+    kPFSampling16Suite,
+    kPFSampling16SuiteVersion1
+);
+
+define_suite!(
+    /// Nearest neighbor, subpixel and area sampling of 32-bit float layers. See [`Sampling`] for the details of each function.
+    SamplingFloatSuite,
+    PF_SamplingFloatSuite1,
+    kPFSamplingFloatSuite,
+    kPFSamplingFloatSuiteVersion1
+);
+
+define_suite!(
+    /// Sets up sampling like [`UtilCallbacks::begin_sampling()`], and returns the host's batch sampling functions.
+    ///
+    /// The batch functions are opaque and their signature isn't documented in the SDK.
+    /// To sample in tight loops, use [`Sampling::batch()`] instead, which looks up the sampling functions once.
+    BatchSamplingSuite,
+    PF_BatchSamplingSuite1,
+    kPFBatchSamplingSuite,
+    kPFBatchSamplingSuiteVersion1
+);
+
+/// A sampling function of [`Sampling8Suite`], [`Sampling16Suite`] or [`SamplingFloatSuite`].
+pub type SampleFn<P> = unsafe extern "C" fn(effect_ref: PF_ProgPtr, x: PF_Fixed, y: PF_Fixed, params: *const PF_SampPB, dst_pixel: *mut P) -> PF_Err;
+
+/// Pixel types which can be sampled by the host, with the suite that samples them.
+pub trait SamplePixel: PixelType {
+    type Suite;
+
+    /// Acquire the sampling suite for this bit depth.
+    fn suite() -> Result<Self::Suite, Error>;
+
+    /// The nearest neighbor, subpixel and area sampling functions of `suite`, in this order.
+    fn functions(suite: &Self::Suite) -> Result<[SampleFn<Self>; 3], Error>;
+}
+
+macro_rules! impl_sampling_suite {
+    ($suite:ident, $pixel:ty, $nn:ident, $subpixel:ident, $area:ident) => {
+        impl $suite {
+            /// Acquire this suite from the host. Returns error if the suite is not available.
+            /// Suite is released on drop.
+            pub fn new() -> Result<Self, Error> {
+                crate::Suite::new()
+            }
+
+            /// Returns the nearest pixel to `(x, y)`.
+            pub fn nn_sample(&self, effect_ref: impl AsPtr<PF_ProgPtr>, x: f32, y: f32, params: &PF_SampPB) -> Result<$pixel, Error> {
+                call_suite_fn_single!(self, $nn -> $pixel, effect_ref.as_ptr(), Fixed::from(x).as_fixed(), Fixed::from(y).as_fixed(), params)
+            }
+
+            /// Interpolates the alpha weighted mix of colors at `(x, y)`.
+            pub fn subpixel_sample(&self, effect_ref: impl AsPtr<PF_ProgPtr>, x: f32, y: f32, params: &PF_SampPB) -> Result<$pixel, Error> {
+                call_suite_fn_single!(self, $subpixel -> $pixel, effect_ref.as_ptr(), Fixed::from(x).as_fixed(), Fixed::from(y).as_fixed(), params)
+            }
+
+            /// Averages the rectangle of `x_radius` by `y_radius` of `params` around `(x, y)`.
+            pub fn area_sample(&self, effect_ref: impl AsPtr<PF_ProgPtr>, x: f32, y: f32, params: &PF_SampPB) -> Result<$pixel, Error> {
+                call_suite_fn_single!(self, $area -> $pixel, effect_ref.as_ptr(), Fixed::from(x).as_fixed(), Fixed::from(y).as_fixed(), params)
+            }
+        }
+
+        impl SamplePixel for $pixel {
+            type Suite = $suite;
+
+            fn suite() -> Result<Self::Suite, Error> {
+                $suite::new()
+            }
+
+            fn functions(suite: &Self::Suite) -> Result<[SampleFn<Self>; 3], Error> {
+                let suite = unsafe { &*suite.suite_ptr };
+                Ok([
+                    suite.$nn.ok_or(Error::MissingSuite)?,
+                    suite.$subpixel.ok_or(Error::MissingSuite)?,
+                    suite.$area.ok_or(Error::MissingSuite)?,
+                ])
+            }
+        }
+    };
+}
+
+impl_sampling_suite!(Sampling8Suite,     Pixel8,   nn_sample,       subpixel_sample,       area_sample);
+impl_sampling_suite!(Sampling16Suite,    Pixel16,  nn_sample16,     subpixel_sample16,     area_sample16);
+impl_sampling_suite!(SamplingFloatSuite, PixelF32, nn_sample_float, subpixel_sample_float, area_sample_float);
+
+impl BatchSamplingSuite {
+    /// Acquire this suite from the host. Returns error if the suite is not available.
+    /// Suite is released on drop.
+    pub fn new() -> Result<Self, Error> {
+        crate::Suite::new()
+    }
+
+    /// Same as [`UtilCallbacks::begin_sampling()`], without the guard. Must be paired with [`end_sampling()`](Self::end_sampling).
+    pub fn begin_sampling(&self, effect_ref: impl AsPtr<PF_ProgPtr>, quality: Quality, mode_flags: ModeFlags, params: &mut PF_SampPB) -> Result<(), Error> {
+        call_suite_fn!(self, begin_sampling, effect_ref.as_ptr(), quality.into(), mode_flags.into(), params)
+    }
+
+    pub fn end_sampling(&self, effect_ref: impl AsPtr<PF_ProgPtr>, quality: Quality, mode_flags: ModeFlags, params: &mut PF_SampPB) -> Result<(), Error> {
+        call_suite_fn!(self, end_sampling, effect_ref.as_ptr(), quality.into(), mode_flags.into(), params)
+    }
+
+    /// Returns the host's opaque 8-bit batch sampling function.
+    pub fn batch_func(&self, effect_ref: impl AsPtr<PF_ProgPtr>, quality: Quality, mode_flags: ModeFlags, params: &PF_SampPB) -> Result<ae_sys::PF_BatchSampleFunc, Error> {
+        call_suite_fn_single!(self, get_batch_func -> ae_sys::PF_BatchSampleFunc, effect_ref.as_ptr(), quality.into(), mode_flags.into(), params)
+    }
+
+    /// Returns the host's opaque 16-bit batch sampling function.
+    pub fn batch_func16(&self, effect_ref: impl AsPtr<PF_ProgPtr>, quality: Quality, mode_flags: ModeFlags, params: &PF_SampPB) -> Result<ae_sys::PF_BatchSample16Func, Error> {
+        call_suite_fn_single!(self, get_batch_func16 -> ae_sys::PF_BatchSample16Func, effect_ref.as_ptr(), quality.into(), mode_flags.into(), params)
+    }
+}
@@ -264,6 +264,7 @@ impl UtilCallbacks {
             params: unsafe { std::mem::zeroed() },
             quality: quality.into(),
             mode_flags: mode_flags.into(),
+            float_suite: std::cell::OnceCell::new(),
         };
         let _ = call_fn!(self, begin_sampling, quality.into(), mode_flags.into(), &mut params.params)?;
         Ok(params)
@@ -751,6 +752,7 @@ pub struct Sampling {
     pub params: ae_sys::PF_SampPB,
     pub quality: ae_sys::PF_Quality,
     pub mode_flags: ae_sys::PF_ModeFlags,
+    float_suite: std::cell::OnceCell<pf::suites::SamplingFloat>,
 }
 impl Sampling {
     /// Use this to interpolate the appropriate alpha weighted mix of colors at a non-integral point in a source image, in high quality.
@@ -807,6 +809,51 @@ impl Sampling {
             }
         }
     }
+
+    /// Use this to interpolate the appropriate alpha weighted mix of colors at a non-integral point in a source image, in high quality.
+    /// Nearest neighbor sample is used in low quality.
+    ///
+    /// The float sampling suite is acquired on the first call and kept until the sampling ends.
+    /// In tight loops, [`batch()`](Self::batch) also skips looking up the function for every sample.
+    pub fn subpixel_sample32(&self, x: f32, y: f32) -> Result<PixelF32, Error> {
+        self.float_suite()?.subpixel_sample(unsafe { (*self.in_data_ptr).effect_ref }, x, y, &self.params)
+    }
+
+    /// Use this to calculate the appropriate alpha weighted average of an axis-aligned non-integral rectangle of color in a source image, in high quality.
+    /// Nearest neighbor in low quality.
+    ///
+    /// Like [`subpixel_sample32()`](Self::subpixel_sample32), this reuses the float sampling suite.
+    pub fn area_sample32(&self, x: f32, y: f32) -> Result<PixelF32, Error> {
+        self.float_suite()?.area_sample(unsafe { (*self.in_data_ptr).effect_ref }, x, y, &self.params)
+    }
+
+    fn float_suite(&self) -> Result<&pf::suites::SamplingFloat, Error> {
+        if let Some(suite) = self.float_suite.get() {
+            return Ok(suite);
+        }
+        let suite = pf::suites::SamplingFloat::new()?;
+        Ok(self.float_suite.get_or_init(|| suite))
+    }
+
+    /// Looks up the sampling functions for pixels of type `P` once, so tight loops don't go through the suite for every sample.
+    ///
+    /// ```ignore
+    /// let mut sampling = in_data.utils().begin_sampling(ae::Quality::Hi, ae::ModeFlags::AlphaStraight)?;
+    /// sampling.params.src = in_layer.as_mut_ptr();
+    /// let sampler = sampling.batch::<ae::PixelF32>()?;
+    /// for (x, y) in points {
+    ///     let pixel = sampler.subpixel_sample(x, y)?;
+    /// }
+    /// ```
+    pub fn batch<P: SamplePixel>(&self) -> Result<BatchSampler<'_, P>, Error> {
+        let suite = P::suite()?;
+        Ok(BatchSampler {
+            sampling: self,
+            effect_ref: unsafe { (*self.in_data_ptr).effect_ref },
+            functions: P::functions(&suite)?,
+            _suite: suite,
+        })
+    }
 }
 impl Drop for Sampling {
     fn drop(&mut self) {
@@ -820,3 +867,43 @@ impl Drop for Sampling {
         }
     }
 }
+
+/// Sampling functions of one bit depth, looked up once by [`Sampling::batch()`].
+pub struct BatchSampler<'a, P: SamplePixel> {
+    sampling: &'a Sampling,
+    effect_ref: PF_ProgPtr,
+    functions: [SampleFn<P>; 3],
+    _suite: P::Suite,
+}
+impl<P: SamplePixel> BatchSampler<'_, P> {
+    fn sample(&self, function: SampleFn<P>, x: f32, y: f32) -> Result<P, Error> {
+        let mut pixel = std::mem::MaybeUninit::<P>::uninit();
+        match unsafe { function(self.effect_ref, Fixed::from(x).as_fixed(), Fixed::from(y).as_fixed(), &self.sampling.params, pixel.as_mut_ptr()) } {
+            0 => Ok(unsafe { pixel.assume_init() }),
+            e => Err(e.into()),
+        }
+    }
+
+    /// Returns the nearest pixel to `(x, y)`.
+    pub fn nn_sample(&self, x: f32, y: f32) -> Result<P, Error> {
+        self.sample(self.functions[0], x, y)
+    }
+
+    /// See [`Sampling::subpixel_sample()`].
+    pub fn subpixel_sample(&self, x: f32, y: f32) -> Result<P, Error> {
+        self.sample(self.functions[1], x, y)
+    }
+
+    /// See [`Sampling::area_sample()`].
+    pub fn area_sample(&self, x: f32, y: f32) -> Result<P, Error> {
+        self.sample(self.functions[2], x, y)
+    }
+
+    /// Subpixel samples each of `points` into the pixel with the same index in `dst`. Stops at the shorter of the two.
+    pub fn subpixel_sample_into(&self, points: impl IntoIterator<Item = (f32, f32)>, dst: &mut [P]) -> Result<(), Error> {
+        for ((x, y), pixel) in points.into_iter().zip(dst.iter_mut()) {
+            *pixel = self.sample(self.functions[1], x, y)?;
+        }
+        Ok(())
+    }
+}
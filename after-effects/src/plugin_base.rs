@@ -28,6 +28,7 @@
 ///     ) -> Result<(), Error>;
 /// }
 /// ```
+/// In `can_load`, [`HostInfo::from_host_strings()`](crate::HostInfo::from_host_strings) parses the host name and version.
 ///
 /// # 2. The instance type
 /// This type will be created in [`Command::SequenceSetup`](crate::pf::Command::SequenceSetup) and destroyed in [`Command::SequenceSetdown`](crate::pf::Command::SequenceSetdown).
@@ -65,6 +66,7 @@
 ///
 /// The `unflatten()` method will be called to restore the instance from the serialized data. The `u16` parameter specifies the version of the serialized data,
 /// so you can always restore the data correctly even if user updates your plugin and tries to load a project file with older data version in it.
+/// [`VersionedData`](crate::VersionedData) implements this with a chain of serde structs, one for each version, migrated with `From`.
 ///
 /// The `PluginState` struct allows you to access the global struct, instance struct, parameters, and input/output data in your plugin's command selectors.
 /// ```ignore
@@ -240,6 +242,8 @@ macro_rules! define_effect {
 
             // Allocate or restore global data pointer
             let mut global_handle = if cmd == RawCommand::GlobalSetup {
+                $crate::HostInfo::set_current(in_data.host_info());
+
                 // Allocate global data
                 pf::Handle::new(GlobalData {
                     params_map: std::sync::OnceLock::new(),
@@ -284,7 +288,7 @@ macro_rules! define_effect {
                 out_data
             };
 
-            let command = Command::from_entry_point(cmd, in_data_ptr, params, output, extra);
+            let command = Command::from_entry_point(cmd, in_data_ptr, out_data_ptr, params, output, extra);
 
             let global_err = plugin_state.global.handle_command(command, in_data, out_data, plugin_state.params);
             let mut sequence_err = None;
@@ -299,7 +303,7 @@ macro_rules! define_effect {
                 };
                 let in_data = InData::from_raw(in_data_ptr);
                 let out_data = OutData::from_raw(out_data_ptr);
-                let command = Command::from_entry_point(cmd, in_data_ptr, params, output, extra);
+                let command = Command::from_entry_point(cmd, in_data_ptr, out_data_ptr, params, output, extra);
 
                 sequence_err = Some(inst.handle_command(&mut plugin_state, command));
 
@@ -391,6 +395,8 @@ macro_rules! define_effect {
             let in_host_name = std::ffi::CStr::from_ptr(in_host_name);
             let in_host_version = std::ffi::CStr::from_ptr(in_host_version);
 
+            $crate::HostInfo::set_loader_strings(&in_host_name.to_string_lossy(), &in_host_version.to_string_lossy());
+
             if !<$global_type>::can_load(in_host_name.to_str().unwrap(), in_host_version.to_str().unwrap()) {
                 // Plugin said we don't want to load in this host, so exit here
                 return $crate::sys::PF_Err_INVALID_CALLBACK as $crate::sys::PF_Err;
@@ -0,0 +1,127 @@
+//! Per-thread cache of acquired suite pointers.
+//!
+//! Suites defined with `define_suite!` are acquired through this cache, so constructing a suite wrapper in a hot path
+//! only calls `AcquireSuite` the first time on each thread. Every entry counts the wrappers using it.
+//! Unused entries are released when the outermost [`PicaBasicSuite`] scope of the thread ends,
+//! and entries still in use when their last wrapper is dropped.
+//!
+//! The cache is thread-local, so it's safe with Multi-Frame Rendering, where every render thread has its own scope.
+//! Nested entry points with a different `SPBasicSuite` get their own entries.
+
+use crate::*;
+use std::cell::Cell;
+
+struct CachedSuite {
+    pica_basic_suite_ptr: *const ae_sys::SPBasicSuite,
+    name: &'static [u8],
+    version: i32,
+    suite_ptr: *const std::ffi::c_void,
+    ref_count: usize,
+}
+
+thread_local!(
+    static SUITE_CACHE: RefCell<Vec<CachedSuite>> = const { RefCell::new(Vec::new()) };
+    static SCOPE_DEPTH: Cell<usize> = const { Cell::new(0) };
+);
+
+/// Get the suite `name` at `version`, acquiring it if it's not cached yet. Each successful call must be paired with [`release()`].
+pub(crate) fn acquire(pica_basic_suite_ptr: *const ae_sys::SPBasicSuite, name: &'static [u8], version: i32) -> Result<*const std::ffi::c_void, Error> {
+    let cached = SUITE_CACHE.with_borrow_mut(|cache| {
+        let entry = cache.iter_mut().find(|x| x.pica_basic_suite_ptr == pica_basic_suite_ptr && x.version == version && x.name == name)?;
+        entry.ref_count += 1;
+        Some(entry.suite_ptr)
+    });
+    if let Some(suite_ptr) = cached {
+        return Ok(suite_ptr);
+    }
+
+    if pica_basic_suite_ptr.is_null() {
+        return Err(Error::MissingSuite);
+    }
+    let mut suite_ptr = ptr::null();
+    let acquire_suite = unsafe { (*pica_basic_suite_ptr).AcquireSuite }.ok_or(Error::MissingSuite)?;
+    if unsafe { acquire_suite(name.as_ptr() as *const _, version as _, &mut suite_ptr) } as u32 != ae_sys::kSPNoError {
+        return Err(Error::MissingSuite);
+    }
+    SUITE_CACHE.with_borrow_mut(|cache| cache.push(CachedSuite { pica_basic_suite_ptr, name, version, suite_ptr, ref_count: 1 }));
+    Ok(suite_ptr)
+}
+
+/// Release a suite acquired with [`acquire()`]. It's released from the host only if it's unused and outside of a [`PicaBasicSuite`] scope.
+pub(crate) fn release(pica_basic_suite_ptr: *const ae_sys::SPBasicSuite, name: &'static [u8], version: i32) {
+    let unused = SUITE_CACHE.with_borrow_mut(|cache| {
+        let index = cache.iter().position(|x| x.pica_basic_suite_ptr == pica_basic_suite_ptr && x.version == version && x.name == name)?;
+        cache[index].ref_count = cache[index].ref_count.saturating_sub(1);
+        if cache[index].ref_count == 0 && SCOPE_DEPTH.get() == 0 {
+            Some(cache.swap_remove(index))
+        } else {
+            None
+        }
+    });
+    if let Some(suite) = unused {
+        release_from_host(&suite);
+    }
+}
+
+/// Called when a [`PicaBasicSuite`] scope starts.
+pub(crate) fn enter_scope() {
+    SCOPE_DEPTH.set(SCOPE_DEPTH.get() + 1);
+}
+
+/// Called when a [`PicaBasicSuite`] scope ends. Releases the unused suites when leaving the outermost scope.
+pub(crate) fn leave_scope() {
+    let depth = SCOPE_DEPTH.get().saturating_sub(1);
+    SCOPE_DEPTH.set(depth);
+    if depth > 0 {
+        return;
+    }
+    let unused = SUITE_CACHE.with_borrow_mut(|cache| {
+        let (unused, used): (Vec<_>, Vec<_>) = std::mem::take(cache).into_iter().partition(|x| x.ref_count == 0);
+        *cache = used;
+        unused
+    });
+    for suite in unused.iter() {
+        release_from_host(suite);
+    }
+}
+
+fn release_from_host(suite: &CachedSuite) {
+    if let Some(release_suite) = unsafe { (*suite.pica_basic_suite_ptr).ReleaseSuite } {
+        unsafe { release_suite(suite.name.as_ptr() as *const _, suite.version as _) };
+    }
+}
+
+#[test]
+fn test_suite_cache() {
+    use std::sync::atomic::{ AtomicUsize, Ordering };
+    static ACQUIRED: AtomicUsize = AtomicUsize::new(0);
+    static RELEASED: AtomicUsize = AtomicUsize::new(0);
+    unsafe extern "C" fn acquire_suite(_: *const std::ffi::c_char, _: ae_sys::int32, suite: *mut *const std::ffi::c_void) -> ae_sys::SPErr {
+        ACQUIRED.fetch_add(1, Ordering::SeqCst);
+        unsafe { *suite = std::ptr::dangling::<std::ffi::c_void>() };
+        0
+    }
+    unsafe extern "C" fn release_suite(_: *const std::ffi::c_char, _: ae_sys::int32) -> ae_sys::SPErr {
+        RELEASED.fetch_add(1, Ordering::SeqCst);
+        0
+    }
+    let mut basic: ae_sys::SPBasicSuite = unsafe { std::mem::zeroed() };
+    basic.AcquireSuite = Some(acquire_suite);
+    basic.ReleaseSuite = Some(release_suite);
+    let name: &'static [u8] = b"Test Suite\0";
+
+    let scope = PicaBasicSuite::from_sp_basic_suite_raw(&basic);
+    for _ in 0..3 {
+        acquire(&basic, name, 1).unwrap();
+        release(&basic, name, 1);
+    }
+    let nested = PicaBasicSuite::from_sp_basic_suite_raw(&basic);
+    acquire(&basic, name, 1).unwrap();
+    drop(nested);
+    assert_eq!((ACQUIRED.load(Ordering::SeqCst), RELEASED.load(Ordering::SeqCst)), (1, 0));
+    drop(scope);
+    // Still in use after the outermost scope, released with the last user
+    assert_eq!(RELEASED.load(Ordering::SeqCst), 0);
+    release(&basic, name, 1);
+    assert_eq!(RELEASED.load(Ordering::SeqCst), 1);
+}
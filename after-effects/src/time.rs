@@ -0,0 +1,438 @@
+use crate::*;
+use std::cmp::Ordering;
+use std::hash::{ Hash, Hasher };
+use std::ops::{ Add, AddAssign, Div, Mul, Neg, Sub, SubAssign };
+use std::str::FromStr;
+
+/// A point in time or a duration, as `value / scale` seconds.
+///
+/// Comparison and hashing are by the rational value, so `1/2` equals `2/4`.
+/// Adding, subtracting and negating keep the common scale of both times, so `1/30 + 1/30` is `2/30`.
+/// Other arithmetic reduces the result. When the exact result doesn't fit in `i32 / u32`, the scale is reduced until it fits, losing precision.
+#[derive(Debug, Copy, Clone)]
+pub struct Time {
+    pub value: i32,
+    pub scale: u32,
+}
+define_struct_conv!(ae_sys::A_Time, Time { value, scale });
+
+/// A rational number, like the stretch factor of a layer.
+#[derive(Debug, Copy, Clone)]
+pub struct Ratio {
+    pub num: i32,
+    pub den: u32,
+}
+define_struct_conv!(ae_sys::A_Ratio, Ratio { num, den });
+
+impl Time {
+    pub const ZERO: Self = Self { value: 0, scale: 1 };
+
+    pub const fn new(value: i32, scale: u32) -> Self {
+        Self { value, scale }
+    }
+
+    /// The same time with the smallest possible scale.
+    pub fn normalize(self) -> Self {
+        let (value, scale) = reduce(self.value as i128, self.scale as i128);
+        Self { value: value as _, scale: scale as _ }
+    }
+
+    /// Time of the start of `frame`.
+    /// * `frame_rate` - Frames per second, for example `30000/1001` for 29.97 fps.
+    pub fn from_frame(frame: i64, frame_rate: Ratio) -> Self {
+        Self::from_i128(frame as i128 * frame_rate.den as i128, frame_rate.num as i128)
+    }
+    /// The frame which contains this time. Rounds towards negative infinity.
+    pub fn frame(self, frame_rate: Ratio) -> i64 {
+        let num = self.value as i128 * frame_rate.num as i128;
+        let den = self.scale as i128 * frame_rate.den as i128;
+        if den == 0 {
+            return 0;
+        }
+        num.div_euclid(den) as i64
+    }
+
+    /// Timecode of the frame which contains this time.
+    pub fn to_timecode(self, frame_rate: Ratio, drop_frame: bool) -> Timecode {
+        Timecode::from_frame(self.frame(frame_rate), frame_rate, drop_frame)
+    }
+    /// Time of the start of the frame of `timecode`.
+    pub fn from_timecode(timecode: Timecode, frame_rate: Ratio) -> Self {
+        Self::from_frame(timecode.frame(frame_rate), frame_rate)
+    }
+
+    pub fn abs(self) -> Self {
+        if self.value < 0 { -self } else { self }
+    }
+
+    /// `value / scale` with the widest intermediate values, reduced to fit.
+    fn from_i128(value: i128, scale: i128) -> Self {
+        let (value, scale) = fit(value, scale, u32::MAX as i128);
+        Self { value: value as _, scale: scale as _ }
+    }
+
+    /// `value / scale` without reducing it, unless it doesn't fit.
+    fn from_i128_unreduced(value: i128, scale: i128) -> Self {
+        if value >= i32::MIN as i128 && value <= i32::MAX as i128 && scale > 0 && scale <= u32::MAX as i128 {
+            Self { value: value as _, scale: scale as _ }
+        } else {
+            Self::from_i128(value, scale)
+        }
+    }
+
+    /// `self + sign * rhs` in the least common scale of both times.
+    fn add_signed(self, rhs: Self, sign: i128) -> Self {
+        if self.scale == 0 || rhs.scale == 0 {
+            return Self::from_i128(
+                self.value as i128 * rhs.scale as i128 + sign * rhs.value as i128 * self.scale as i128,
+                self.scale as i128 * rhs.scale as i128,
+            );
+        }
+        let scale = self.scale as i128 / gcd(self.scale as u128, rhs.scale as u128) as i128 * rhs.scale as i128;
+        Self::from_i128_unreduced(
+            self.value as i128 * (scale / self.scale as i128) + sign * rhs.value as i128 * (scale / rhs.scale as i128),
+            scale,
+        )
+    }
+}
+
+impl From<Time> for f64 {
+    #[inline]
+    fn from(time: Time) -> Self {
+        debug_assert!(time.scale != 0);
+        time.value as Self / time.scale as Self
+    }
+}
+impl From<Time> for f32 {
+    #[inline]
+    fn from(time: Time) -> Self {
+        debug_assert!(time.scale != 0);
+        time.value as Self / time.scale as Self
+    }
+}
+
+impl PartialEq for Time {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+impl Eq for Time { }
+impl PartialOrd for Time {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for Time {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.value as i64 * other.scale as i64).cmp(&(other.value as i64 * self.scale as i64))
+    }
+}
+impl Hash for Time {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let time = self.normalize();
+        (time.value, time.scale).hash(state);
+    }
+}
+
+impl Add for Time {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        self.add_signed(rhs, 1)
+    }
+}
+impl Sub for Time {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        self.add_signed(rhs, -1)
+    }
+}
+impl AddAssign for Time {
+    fn add_assign(&mut self, rhs: Self) { *self = *self + rhs; }
+}
+impl SubAssign for Time {
+    fn sub_assign(&mut self, rhs: Self) { *self = *self - rhs; }
+}
+impl Neg for Time {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::from_i128_unreduced(-(self.value as i128), self.scale as i128)
+    }
+}
+/// Scale a duration, for example by the stretch factor of a layer.
+impl Mul<Ratio> for Time {
+    type Output = Self;
+    fn mul(self, rhs: Ratio) -> Self {
+        Self::from_i128(self.value as i128 * rhs.num as i128, self.scale as i128 * rhs.den as i128)
+    }
+}
+impl Div<Ratio> for Time {
+    type Output = Self;
+    fn div(self, rhs: Ratio) -> Self {
+        Self::from_i128(self.value as i128 * rhs.den as i128, self.scale as i128 * rhs.num as i128)
+    }
+}
+/// How many times `rhs` fits in `self`.
+impl Div for Time {
+    type Output = Ratio;
+    fn div(self, rhs: Self) -> Ratio {
+        Ratio::from_i128(self.value as i128 * rhs.scale as i128, self.scale as i128 * rhs.value as i128)
+    }
+}
+
+macro_rules! impl_rational {
+    ($name:ident) => {
+        impl $name {
+            pub const ONE: Self = Self { num: 1, den: 1 };
+
+            pub const fn new(num: i32, den: u32) -> Self {
+                Self { num, den }
+            }
+
+            /// The same ratio with the smallest possible denominator.
+            pub fn normalize(self) -> Self {
+                let (num, den) = reduce(self.num as i128, self.den as i128);
+                Self { num: num as _, den: den as _ }
+            }
+
+            /// `1 / self`. The sign moves to the numerator.
+            pub fn recip(self) -> Self {
+                Self::from_i128(self.den as i128, self.num as i128)
+            }
+
+            fn from_i128(num: i128, den: i128) -> Self {
+                let (num, den) = fit(num, den, u32::MAX as i128);
+                Self { num: num as _, den: den as _ }
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.cmp(other) == Ordering::Equal
+            }
+        }
+        impl Eq for $name { }
+        impl PartialOrd for $name {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for $name {
+            fn cmp(&self, other: &Self) -> Ordering {
+                (self.num as i64 * other.den as i64).cmp(&(other.num as i64 * self.den as i64))
+            }
+        }
+        impl Hash for $name {
+            fn hash<H: Hasher>(&self, state: &mut H) {
+                let ratio = self.normalize();
+                (ratio.num, ratio.den).hash(state);
+            }
+        }
+        impl Mul for $name {
+            type Output = Self;
+            fn mul(self, rhs: Self) -> Self {
+                Self::from_i128(self.num as i128 * rhs.num as i128, self.den as i128 * rhs.den as i128)
+            }
+        }
+        impl Div for $name {
+            type Output = Self;
+            fn div(self, rhs: Self) -> Self {
+                Self::from_i128(self.num as i128 * rhs.den as i128, self.den as i128 * rhs.num as i128)
+            }
+        }
+        impl Neg for $name {
+            type Output = Self;
+            fn neg(self) -> Self {
+                Self::from_i128(-(self.num as i128), self.den as i128)
+            }
+        }
+    };
+}
+impl_rational!(Ratio);
+impl_rational!(RationalScale);
+
+impl From<Ratio> for f64 {
+    #[inline]
+    fn from(ratio: Ratio) -> Self {
+        debug_assert!(ratio.den != 0);
+        ratio.num as Self / ratio.den as Self
+    }
+}
+impl From<Ratio> for f32 {
+    #[inline]
+    fn from(ratio: Ratio) -> Self {
+        debug_assert!(ratio.den != 0);
+        ratio.num as Self / ratio.den as Self
+    }
+}
+impl From<RationalScale> for Ratio {
+    fn from(ratio: RationalScale) -> Self {
+        Self { num: ratio.num, den: ratio.den }
+    }
+}
+impl From<Ratio> for RationalScale {
+    fn from(ratio: Ratio) -> Self {
+        Self { num: ratio.num, den: ratio.den }
+    }
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.max(1)
+}
+
+/// Reduce `num / den` and move the sign to `num`.
+fn reduce(num: i128, den: i128) -> (i128, i128) {
+    let (num, den) = if den < 0 { (-num, -den) } else { (num, den) };
+    if den == 0 {
+        return (num, den);
+    }
+    let gcd = gcd(num.unsigned_abs(), den as u128) as i128;
+    (num / gcd, den / gcd)
+}
+
+/// Reduce `num / den` so that `num` fits in `i32` and `den` in `max_den`, halving both as long as needed.
+fn fit(num: i128, den: i128, max_den: i128) -> (i128, i128) {
+    let (mut num, mut den) = reduce(num, den);
+    while (num > i32::MAX as i128 || num < i32::MIN as i128 || den > max_den) && den > 1 {
+        // Round half away from zero
+        num = (num + num.signum()) / 2;
+        den /= 2;
+    }
+    (num.clamp(i32::MIN as i128, i32::MAX as i128), den)
+}
+
+/// SMPTE timecode, like `01:00:00:00`, or `01:00:00;00` for drop-frame.
+///
+/// Drop-frame timecode skips frame numbers `0` and `1` (`0` to `3` at 60 fps) at the start of every minute except every tenth one,
+/// so that it stays in sync with the clock at 29.97 and 59.94 fps. It's only used for frame rates which round to a multiple of 30,
+/// for other rates `drop_frame` is ignored.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Timecode {
+    pub negative: bool,
+    pub hours: u32,
+    pub minutes: u32,
+    pub seconds: u32,
+    pub frames: u32,
+    pub drop_frame: bool,
+}
+
+impl Timecode {
+    /// Timecode of `frame` at the given frame rate.
+    pub fn from_frame(frame: i64, frame_rate: Ratio, drop_frame: bool) -> Self {
+        let fps = nominal_fps(frame_rate);
+        let dropped = dropped_frames(fps, drop_frame);
+        let negative = frame < 0;
+        let mut frame = frame.abs();
+        if dropped > 0 {
+            let per_10_minutes = fps * 600 - dropped * 9;
+            let per_minute = fps * 60 - dropped;
+            let (tens, rem) = (frame / per_10_minutes, frame % per_10_minutes);
+            frame += dropped * 9 * tens;
+            if rem > dropped {
+                frame += dropped * ((rem - dropped) / per_minute);
+            }
+        }
+        Self {
+            negative,
+            hours: (frame / (fps * 3600)) as u32,
+            minutes: (frame / (fps * 60) % 60) as u32,
+            seconds: (frame / fps % 60) as u32,
+            frames: (frame % fps) as u32,
+            drop_frame: dropped > 0,
+        }
+    }
+
+    /// Frame number of this timecode at the given frame rate.
+    pub fn frame(&self, frame_rate: Ratio) -> i64 {
+        let fps = nominal_fps(frame_rate);
+        let dropped = dropped_frames(fps, self.drop_frame);
+        let minutes = self.hours as i64 * 60 + self.minutes as i64;
+        let frame = (minutes * 60 + self.seconds as i64) * fps + self.frames as i64 - dropped * (minutes - minutes / 10);
+        if self.negative { -frame } else { frame }
+    }
+}
+
+/// Frame rate rounded to whole frames per second, which is what timecode counts in.
+fn nominal_fps(frame_rate: Ratio) -> i64 {
+    if frame_rate.den == 0 {
+        return 1;
+    }
+    ((frame_rate.num as i64 * 2 + frame_rate.den as i64) / (frame_rate.den as i64 * 2)).max(1)
+}
+/// Frame numbers skipped at the start of each minute.
+fn dropped_frames(nominal_fps: i64, drop_frame: bool) -> i64 {
+    if drop_frame && nominal_fps % 30 == 0 { nominal_fps / 15 } else { 0 }
+}
+
+impl std::fmt::Display for Timecode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{:02}:{:02}:{:02}{}{:02}",
+            if self.negative { "-" } else { "" },
+            self.hours, self.minutes, self.seconds,
+            if self.drop_frame { ';' } else { ':' },
+            self.frames
+        )
+    }
+}
+/// Parses `hh:mm:ss:ff`, with `;` or `.` before the frames for drop-frame. Fails with [`Error::InvalidParms`].
+impl FromStr for Timecode {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
+        let parts = s.split([':', ';', '.']).map(|x| x.parse::<u32>().map_err(|_| Error::InvalidParms)).collect::<Result<Vec<_>, _>>()?;
+        let [hours, minutes, seconds, frames] = parts[..] else {
+            return Err(Error::InvalidParms);
+        };
+        if minutes >= 60 || seconds >= 60 {
+            return Err(Error::InvalidParms);
+        }
+        Ok(Self {
+            negative,
+            hours,
+            minutes,
+            seconds,
+            frames,
+            drop_frame: s.contains([';', '.']),
+        })
+    }
+}
+
+#[test]
+fn test_time() {
+    let ntsc = Ratio::new(30000, 1001);
+    assert_eq!(Time::new(1, 2), Time::new(2, 4));
+    assert!(Time::new(1, 3) < Time::new(1, 2));
+    assert_eq!(Time::new(1, 2) - Time::new(1, 3), Time::new(1, 6));
+    assert_eq!((Time::new(1, 2) + Time::new(1, 3)).scale, 6);
+    // The same scale is kept, not reduced
+    let sum = Time::new(1, 30) + Time::new(1, 30);
+    assert_eq!((sum.value, sum.scale), (2, 30));
+    let diff = Time::new(5, 30) - Time::new(2, 30);
+    assert_eq!((diff.value, diff.scale), (3, 30));
+    let neg = -Time::new(4, 30);
+    assert_eq!((neg.value, neg.scale), (-4, 30));
+    let sum = Time::new(1, 30) + Time::new(1, 60);
+    assert_eq!((sum.value, sum.scale), (3, 60));
+    assert_eq!(Time::new(3, 1) * Ratio::new(1, 2), Time::new(3, 2));
+    // Doesn't fit exactly, the scale is reduced
+    let sum = f64::from(Time::new(1_000_000, 3_000_000_000) + Time::new(1_000_000, 3_000_000_001));
+    assert!((sum * 1500.0 - 1.0).abs() < 1e-5);
+
+    let time = Time::from_frame(1800, ntsc);
+    assert_eq!((time.value, time.scale), (3003, 50));
+    assert_eq!(time.frame(ntsc), 1800);
+    assert_eq!((time - Time::new(1, 30000)).frame(ntsc), 1799);
+
+    let timecode = time.to_timecode(ntsc, true);
+    assert_eq!(timecode.to_string(), "00:01:00;02");
+    assert_eq!("00:01:00;02".parse::<Timecode>().unwrap().frame(ntsc), 1800);
+    assert_eq!(Timecode::from_frame(17982, ntsc, true).to_string(), "00:10:00;00");
+    assert_eq!(Timecode::from_frame(90, Ratio::new(25, 1), true).to_string(), "00:00:03:15");
+    assert!("00:61:00:00".parse::<Timecode>().is_err());
+}
@@ -0,0 +1,118 @@
+use crate::*;
+use serde::{ de::DeserializeOwned, Serialize };
+
+/// Sequence data with a version, which can be migrated from the data of older versions of the plug-in.
+///
+/// Each version is its own struct with `VERSION` and the `Previous` version, and converts from the previous one with `From`.
+/// The first version has `type Previous = Self;`. The data is serialized with `bincode`.
+///
+/// In `AdobePluginInstance`, flatten the latest version with [`flatten_versioned()`](Self::flatten_versioned) and unflatten it with
+/// [`unflatten_or_default()`](Self::unflatten_or_default), which decodes the version found in the project and runs the migrations up to the latest one.
+///
+/// ```ignore
+/// #[derive(Default, Serialize, Deserialize)]
+/// struct InstanceV1 { radius: f32 }
+/// impl ae::VersionedData for InstanceV1 {
+///     const VERSION: u16 = 1;
+///     type Previous = Self;
+/// }
+///
+/// #[derive(Default, Serialize, Deserialize)]
+/// struct Instance { radius: f32, samples: u32 }
+/// impl ae::VersionedData for Instance {
+///     const VERSION: u16 = 2;
+///     type Previous = InstanceV1;
+/// }
+/// impl From<InstanceV1> for Instance {
+///     fn from(v1: InstanceV1) -> Self { Self { radius: v1.radius, samples: 16 } }
+/// }
+///
+/// impl AdobePluginInstance for Instance {
+///     fn flatten(&self) -> Result<(u16, Vec<u8>), Error> {
+///         self.flatten_versioned()
+///     }
+///     fn unflatten(version: u16, bytes: &[u8]) -> Result<Self, Error> {
+///         Ok(Self::unflatten_or_default(version, bytes))
+///     }
+///     ...
+/// }
+/// ```
+pub trait VersionedData: Default + Serialize + DeserializeOwned {
+    /// Version of this struct. Must be greater than the version of `Previous`.
+    const VERSION: u16;
+
+    /// The version before this one, or `Self` for the first version.
+    type Previous: VersionedData + Into<Self>;
+
+    /// Serialize this version, with the version number to return from `flatten()`.
+    fn flatten_versioned(&self) -> Result<(u16, Vec<u8>), Error> {
+        let bytes = bincode::serialize(self).map_err(|_| Error::InternalStructDamaged)?;
+        Ok((Self::VERSION, bytes))
+    }
+
+    /// Deserialize data of `version`, this one or an older one, and migrate it to this version.
+    ///
+    /// Returns [`Error::Struct`] if the data can't be decoded, or if `version` is unknown, for example from a newer version of the plug-in.
+    fn unflatten_versioned(version: u16, bytes: &[u8]) -> Result<Self, Error> {
+        if version == Self::VERSION {
+            bincode::deserialize(bytes).map_err(|_| Error::Struct)
+        } else if version < Self::VERSION && Self::Previous::VERSION < Self::VERSION {
+            Self::Previous::unflatten_versioned(version, bytes).map(Into::into)
+        } else {
+            Err(Error::Struct)
+        }
+    }
+
+    /// Like [`unflatten_versioned()`](Self::unflatten_versioned), but logs a warning and returns the default if the data can't be restored,
+    /// so the project still opens with default settings for the effect.
+    fn unflatten_or_default(version: u16, bytes: &[u8]) -> Self {
+        Self::unflatten_versioned(version, bytes).unwrap_or_else(|e| {
+            log::warn!("Failed to restore {} from sequence data version {version} ({} bytes): {e:?}. Using the defaults.", std::any::type_name::<Self>(), bytes.len());
+            Self::default()
+        })
+    }
+}
+
+#[test]
+fn test_versioned_data() {
+    use serde::Deserialize;
+
+    #[derive(Default, Serialize, Deserialize)]
+    struct V1 { radius: f32 }
+    impl VersionedData for V1 {
+        const VERSION: u16 = 1;
+        type Previous = Self;
+    }
+
+    #[derive(Default, Serialize, Deserialize)]
+    struct V2 { radius: f32, samples: u32 }
+    impl VersionedData for V2 {
+        const VERSION: u16 = 2;
+        type Previous = V1;
+    }
+    impl From<V1> for V2 {
+        fn from(v1: V1) -> Self { Self { radius: v1.radius, samples: 16 } }
+    }
+
+    #[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+    struct V3 { radius: f64, samples: u32, name: String }
+    impl VersionedData for V3 {
+        const VERSION: u16 = 3;
+        type Previous = V2;
+    }
+    impl From<V2> for V3 {
+        fn from(v2: V2) -> Self { Self { radius: v2.radius as f64, samples: v2.samples, name: "Migrated".into() } }
+    }
+
+    let (version, bytes) = V1 { radius: 2.0 }.flatten_versioned().unwrap();
+    assert_eq!(V3::unflatten_versioned(version, &bytes).unwrap(), V3 { radius: 2.0, samples: 16, name: "Migrated".into() });
+
+    let v3 = V3 { radius: 1.0, samples: 4, name: "Current".into() };
+    let (version, bytes) = v3.flatten_versioned().unwrap();
+    assert_eq!(version, 3);
+    assert_eq!(V3::unflatten_versioned(version, &bytes).unwrap(), v3);
+
+    assert!(V3::unflatten_versioned(4, &bytes).is_err());
+    assert!(V3::unflatten_versioned(0, &bytes).is_err());
+    assert_eq!(V3::unflatten_or_default(3, &[1]), V3::default());
+}
@@ -0,0 +1,43 @@
+use after_effects as ae;
+
+// An audio-only effect which changes the volume. The PiPL requests 32-bit float audio with `AudioFloatOnly`.
+
+#[derive(Eq, PartialEq, Hash, Clone, Copy, Debug)]
+enum Params { Gain }
+
+#[derive(Default)]
+struct Plugin { }
+
+ae::define_effect!(Plugin, (), Params);
+
+impl AdobePluginGlobal for Plugin {
+    fn can_load(_host_name: &str, _host_version: &str) -> bool { true }
+
+    fn params_setup(&self, params: &mut ae::Parameters<Params>, _: ae::InData, _: ae::OutData) -> Result<(), Error> {
+        params.add(Params::Gain, "Gain (dB)", ae::FloatSliderDef::setup(|f| {
+            f.set_slider_min(-24.0);
+            f.set_slider_max(24.0);
+            f.set_valid_min(-96.0);
+            f.set_valid_max(24.0);
+            f.set_default(0.0);
+            f.set_precision(1);
+        }))
+    }
+
+    fn handle_command(&mut self, cmd: ae::Command, _: ae::InData, _: ae::OutData, params: &mut ae::Parameters<Params>) -> Result<(), ae::Error> {
+        match cmd {
+            ae::Command::AudioRender { mut extra } => {
+                let gain_db = params.get(Params::Gain)?.as_float_slider()?.value();
+                let gain = 10.0f64.powf(gain_db / 20.0) as f32;
+
+                // Samples of all channels are interleaved, so the same gain applies to each of them
+                let (input, output) = extra.buffers()?;
+                for (out_sample, in_sample) in output.iter_mut().zip(input) {
+                    *out_sample = in_sample * gain;
+                }
+            }
+            _ => { }
+        }
+        Ok(())
+    }
+}
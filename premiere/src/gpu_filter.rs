@@ -12,14 +12,14 @@ impl RenderParams {
         }
     }
     /// Clip time of the current render
-    pub fn clip_time(&self) -> i64 {
+    pub fn clip_time(&self) -> PrTime {
         assert!(!self.ptr.is_null());
-        unsafe { (*self.ptr).inClipTime }
+        unsafe { PrTime((*self.ptr).inClipTime) }
     }
     /// Sequence time of the current render
-    pub fn sequence_time(&self) -> i64 {
+    pub fn sequence_time(&self) -> PrTime {
         assert!(!self.ptr.is_null());
-        unsafe { (*self.ptr).inSequenceTime }
+        unsafe { PrTime((*self.ptr).inSequenceTime) }
     }
     pub fn quality(&self) -> RenderQuality {
         assert!(!self.ptr.is_null());
@@ -45,9 +45,9 @@ impl RenderParams {
         assert!(!self.ptr.is_null());
         unsafe { (*self.ptr).inRenderFieldType }
     }
-    pub fn render_ticks_per_frame(&self) -> i64 {
+    pub fn render_ticks_per_frame(&self) -> PrTime {
         assert!(!self.ptr.is_null());
-        unsafe { (*self.ptr).inRenderTicksPerFrame }
+        unsafe { PrTime((*self.ptr).inRenderTicksPerFrame) }
     }
 
     /// GPU rendering is always on full height progressive frames unless outNeedsFieldSeparation is false.
@@ -87,7 +87,8 @@ impl GpuFilterData {
     /// * `time` - The time requested (in Media time)
     ///
     /// Returns the param
-    pub fn param(&self, index: usize, time: i64) -> Result<crate::Param, Error> {
+    pub fn param(&self, index: usize, time: impl Into<PrTime>) -> Result<crate::Param, Error> {
+        let time = time.into().ticks();
         let index = index as i32 - 1; // GPU filters don't include the input frame as first paramter
 
         self.video_segment_suite.param(self.node_id(), index, time)
@@ -107,13 +108,15 @@ impl GpuFilterData {
     /// Returns a tuple containing:
     /// * `keyframe_time` - The time of the next keyframe > inTime
     /// * `keyframe_interpolation_mode` - The temporal interpolation mode of the keyframe
-    pub fn next_keyframe_time(&self, index: usize, time: i64) -> Result<(i64, KeyframeInterpolationMode), Error> {
+    pub fn next_keyframe_time(&self, index: usize, time: impl Into<PrTime>) -> Result<(PrTime, KeyframeInterpolationMode), Error> {
+        let time = time.into().ticks();
         let index = index as i32 - 1; // GPU filters don't include the input frame as first paramter
 
         self.video_segment_suite.next_keyframe_time(self.node_id(), index, time)
     }
 
-    pub fn param_arbitrary_data<T: for<'a> serde::Deserialize<'a>>(&self, index: usize, time: i64) -> Result<T, Error> {
+    pub fn param_arbitrary_data<T: for<'a> serde::Deserialize<'a>>(&self, index: usize, time: impl Into<PrTime>) -> Result<T, Error> {
+        let time = time.into().ticks();
         let ptr = self.param(index, time)?;
         if let crate::Param::MemoryPtr(ptr) = ptr {
             if !ptr.is_null() {
@@ -11,6 +11,9 @@ mod macros;
 mod types;
 pub use types::*;
 
+mod pr_time;
+pub use pr_time::PrTime;
+
 pub(crate) mod pf_suites {
     pub(crate) mod background_frame;
     pub(crate) mod cache_on_load;
@@ -20,12 +23,16 @@ pub(crate) mod pf_suites {
     pub(crate) mod utility;
 }
 pub mod suites {
+    pub(crate) mod audio;                    pub use audio               ::AudioSuite              as Audio;
+    pub(crate) mod error;                    pub use error               ::ErrorSuite              as Error;
     pub(crate) mod gpu_device;               pub use gpu_device          ::GPUDeviceSuite          as GPUDevice;
     pub(crate) mod gpu_image_processing;     pub use gpu_image_processing::GPUImageProcessingSuite as GPUImageProcessing;
     pub(crate) mod memory_manager;           pub use memory_manager      ::MemoryManagerSuite      as MemoryManager;
     pub(crate) mod ppix;                     pub use ppix                ::{PPixSuite              as PPix,
                                                                             PPix2Suite             as PPix2 };
     pub(crate) mod time;                     pub use time                ::TimeSuite               as Time;
+    pub(crate) mod sequence_render;          pub use sequence_render     ::SequenceRenderSuite     as SequenceRender;
+    pub(crate) mod clip_render;              pub use clip_render         ::ClipRenderSuite         as ClipRender;
     pub(crate) mod sequence_info;            pub use sequence_info       ::SequenceInfoSuite       as SequenceInfo;
     pub(crate) mod video_segment;            pub use video_segment       ::VideoSegmentSuite       as VideoSegment;
     pub(crate) mod string;                   pub use string              ::PrStringSuite           as PrString;
@@ -43,13 +50,18 @@ pub mod suites {
 }
 
 pub mod utils {
+    pub mod event_logger;          pub use event_logger::EventLogger;
+    pub mod timeline_graph;        pub use timeline_graph::{Timeline, Segment, Node, NodeKind, NodeInput, NodeParam, Keyframe, ParamValue};
     pub mod video_sequence_parser; pub use video_sequence_parser::VideoSequenceParser;
 }
 
 pub use suites::string::PrString;
 pub use suites::video_segment_properties::*;
 pub use suites::video_segment::VideoSegmentProperties;
-pub use suites::ppix::YUV420PlanarBuffers;
+pub use suites::ppix::{YUV420PlanarBuffers, OwnedPPix};
+pub use suites::sequence_render::{SequenceRenderParams, RenderCacheType, RenderedFrame, AsyncRenderedFrame, VideoRenderer};
+pub use suites::clip_render::ClipFrameFormat;
+pub use suites::error::EventType;
 pub use suites::sequence_info::ImmersiveVideoVRConfiguration;
 pub use pf_suites::background_frame::TransferMode;
 pub use pf_suites::pixel_format::NewWorldFlags;
@@ -9,7 +9,7 @@ macro_rules! pr_acquire_suite_ptr {
 
             let aquire_suite_func = (*($pica)).AcquireSuite.unwrap_or_else(|| unreachable!());
             match aquire_suite_func(
-                premiere_sys::$name.as_ptr() as *const i8,
+                premiere_sys::$name.as_ptr() as *const std::ffi::c_char,
                 premiere_sys::$version as i32,
                 suite_ptr.as_mut_ptr() as *mut *const _ as _,
             ) as u32
@@ -29,7 +29,7 @@ macro_rules! pr_release_suite_ptr {
             }
             let release_suite_func = (*($pica)).ReleaseSuite.unwrap_or_else(|| unreachable!());
             release_suite_func(
-                premiere_sys::$name.as_ptr() as *const i8,
+                premiere_sys::$name.as_ptr() as *const std::ffi::c_char,
                 premiere_sys::$version as i32,
             );
         }
@@ -62,8 +62,8 @@ impl UtilitySuite {
     }
 
     /// Gets the number of ticks per frame, for the media.
-    pub fn media_frame_rate(&self, effect_ref: impl AsPtr<PF_ProgPtr>) -> Result<PrTime, Error> {
-        call_suite_fn_single!(self, GetMediaFrameRate -> PrTime, effect_ref.as_ptr())
+    pub fn media_frame_rate(&self, effect_ref: impl AsPtr<PF_ProgPtr>) -> Result<crate::PrTime, Error> {
+        call_suite_fn_single!(self, GetMediaFrameRate -> pr_sys::PrTime, effect_ref.as_ptr()).map(crate::PrTime)
     }
 
     /// Gets the ID of the timeline containing the clip to which the effect is applied.
@@ -98,8 +98,8 @@ impl UtilitySuite {
     }
 
     /// Retrieves the original (non-interpreted, un-re-timed) frame rate, of the media to which the effect instance is applied.
-    pub fn original_clip_frame_rate(&self, effect_ref: impl AsPtr<PF_ProgPtr>) -> Result<PrTime, Error> {
-        call_suite_fn_single!(self, GetOriginalClipFrameRate -> PrTime, effect_ref.as_ptr())
+    pub fn original_clip_frame_rate(&self, effect_ref: impl AsPtr<PF_ProgPtr>) -> Result<crate::PrTime, Error> {
+        call_suite_fn_single!(self, GetOriginalClipFrameRate -> pr_sys::PrTime, effect_ref.as_ptr()).map(crate::PrTime)
     }
 
     /// Retrieves the source media timecode for the specified frame within the specified layer, with or without transforms and start time offsets applied.
@@ -130,129 +130,129 @@ impl UtilitySuite {
     }
 
     /// Given a specific sequence time, retrieves the source track media timecode for the specified layer parameter.
-    pub fn source_track_media_timecode2(&self, effect_ref: impl AsPtr<PF_ProgPtr>, layer_param_index: u32, apply_transform: bool, add_start_time_offset: bool, sequence_time: PrTime) -> Result<A_long, Error> {
-        call_suite_fn_single!(self, GetSourceTrackMediaTimecode2 -> A_long, effect_ref.as_ptr(), layer_param_index, apply_transform, add_start_time_offset, sequence_time)
+    pub fn source_track_media_timecode2(&self, effect_ref: impl AsPtr<PF_ProgPtr>, layer_param_index: u32, apply_transform: bool, add_start_time_offset: bool, sequence_time: impl Into<crate::PrTime>) -> Result<A_long, Error> {
+        call_suite_fn_single!(self, GetSourceTrackMediaTimecode2 -> A_long, effect_ref.as_ptr(), layer_param_index, apply_transform, add_start_time_offset, sequence_time.into().ticks())
     }
 
     /// Retrieves the clip name used by the specific layer parameter.
-    pub fn source_track_clip_name2(&self, effect_ref: impl AsPtr<PF_ProgPtr>, layer_param_index: u32, get_master_clip_name: bool, sequence_time: PrTime) -> Result<String, Error> {
+    pub fn source_track_clip_name2(&self, effect_ref: impl AsPtr<PF_ProgPtr>, layer_param_index: u32, get_master_clip_name: bool, sequence_time: impl Into<crate::PrTime>) -> Result<String, Error> {
         let mut val: PrSDKString = unsafe { std::mem::zeroed() };
-        call_suite_fn!(self, GetSourceTrackClipName2, effect_ref.as_ptr(), layer_param_index, get_master_clip_name as _, &mut val, sequence_time)?;
+        call_suite_fn!(self, GetSourceTrackClipName2, effect_ref.as_ptr(), layer_param_index, get_master_clip_name as _, &mut val, sequence_time.into().ticks())?;
 
         Ok(PrString(val).into())
     }
 
     /// Retrieves the clip name in use by the specified layer parameter.
-    pub fn source_track_file_name2(&self, effect_ref: impl AsPtr<PF_ProgPtr>, layer_param_index: u32, sequence_time: PrTime) -> Result<String, Error> {
+    pub fn source_track_file_name2(&self, effect_ref: impl AsPtr<PF_ProgPtr>, layer_param_index: u32, sequence_time: impl Into<crate::PrTime>) -> Result<String, Error> {
         let mut val: PrSDKString = unsafe { std::mem::zeroed() };
-        call_suite_fn!(self, GetSourceTrackFileName2, effect_ref.as_ptr(), layer_param_index, &mut val, sequence_time)?;
+        call_suite_fn!(self, GetSourceTrackFileName2, effect_ref.as_ptr(), layer_param_index, &mut val, sequence_time.into().ticks())?;
 
         Ok(PrString(val).into())
     }
 
     /// Retrieves the comment string associated with the specified source track item, at the specified time.
-    pub fn comment_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: PrTime) -> Result<String, Error> {
-        Ok(PrString(call_suite_fn_single!(self, GetCommentString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time)?).into())
+    pub fn comment_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: impl Into<crate::PrTime>) -> Result<String, Error> {
+        Ok(PrString(call_suite_fn_single!(self, GetCommentString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time.into().ticks())?).into())
     }
 
     /// Retrieves the log note associated with the source track, at the specified time.
-    pub fn log_note_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: PrTime) -> Result<String, Error> {
-        Ok(PrString(call_suite_fn_single!(self, GetLogNoteString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time)?).into())
+    pub fn log_note_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: impl Into<crate::PrTime>) -> Result<String, Error> {
+        Ok(PrString(call_suite_fn_single!(self, GetLogNoteString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time.into().ticks())?).into())
     }
 
     /// Retrieves the camera rolll info associated with the source track, at the specified time.
-    pub fn camera_roll_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: PrTime) -> Result<String, Error> {
-        Ok(PrString(call_suite_fn_single!(self, GetCameraRollString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time)?).into())
+    pub fn camera_roll_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: impl Into<crate::PrTime>) -> Result<String, Error> {
+        Ok(PrString(call_suite_fn_single!(self, GetCameraRollString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time.into().ticks())?).into())
     }
 
     /// Retrieves the metadata string associated with the source track, at the specified time.
-    pub fn client_metadata_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: PrTime) -> Result<String, Error> {
-        Ok(PrString(call_suite_fn_single!(self, GetClientMetadataString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time)?).into())
+    pub fn client_metadata_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: impl Into<crate::PrTime>) -> Result<String, Error> {
+        Ok(PrString(call_suite_fn_single!(self, GetClientMetadataString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time.into().ticks())?).into())
     }
 
     /// Retrieves the daily roll string associated with the source track, at the specified time.
-    pub fn daily_roll_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: PrTime) -> Result<String, Error> {
-        Ok(PrString(call_suite_fn_single!(self, GetDailyRollString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time)?).into())
+    pub fn daily_roll_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: impl Into<crate::PrTime>) -> Result<String, Error> {
+        Ok(PrString(call_suite_fn_single!(self, GetDailyRollString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time.into().ticks())?).into())
     }
 
     /// Retrieves the description metadata string associated with the source track, at the specified time.
-    pub fn description_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: PrTime) -> Result<String, Error> {
-        Ok(PrString(call_suite_fn_single!(self, GetDescriptionString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time)?).into())
+    pub fn description_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: impl Into<crate::PrTime>) -> Result<String, Error> {
+        Ok(PrString(call_suite_fn_single!(self, GetDescriptionString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time.into().ticks())?).into())
     }
 
     /// Retrieves the lab roll string associated with the source track, at the specified time.
-    pub fn lab_roll_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: PrTime) -> Result<String, Error> {
-        Ok(PrString(call_suite_fn_single!(self, GetLabRollString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time)?).into())
+    pub fn lab_roll_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: impl Into<crate::PrTime>) -> Result<String, Error> {
+        Ok(PrString(call_suite_fn_single!(self, GetLabRollString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time.into().ticks())?).into())
     }
 
     /// Retrieves the scene string associated with the source track, at the specified time.
-    pub fn scene_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: PrTime) -> Result<String, Error> {
-        Ok(PrString(call_suite_fn_single!(self, GetSceneString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time)?).into())
+    pub fn scene_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: impl Into<crate::PrTime>) -> Result<String, Error> {
+        Ok(PrString(call_suite_fn_single!(self, GetSceneString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time.into().ticks())?).into())
     }
 
     /// Retrieves the shot string associated with the source track item, at the specified time.
-    pub fn shot_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: PrTime) -> Result<String, Error> {
-        Ok(PrString(call_suite_fn_single!(self, GetShotString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time)?).into())
+    pub fn shot_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: impl Into<crate::PrTime>) -> Result<String, Error> {
+        Ok(PrString(call_suite_fn_single!(self, GetShotString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time.into().ticks())?).into())
     }
 
     /// Retrieves the tape name string associated with the source track item, at the specified time.
-    pub fn tape_name_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: PrTime) -> Result<String, Error> {
-        Ok(PrString(call_suite_fn_single!(self, GetTapeNameString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time)?).into())
+    pub fn tape_name_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: impl Into<crate::PrTime>) -> Result<String, Error> {
+        Ok(PrString(call_suite_fn_single!(self, GetTapeNameString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time.into().ticks())?).into())
     }
 
     /// Retrieves a string representing the video codec associated with the source track item, at the specified time.
-    pub fn video_codec_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: PrTime) -> Result<String, Error> {
-        Ok(PrString(call_suite_fn_single!(self, GetVideoCodecString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time)?).into())
+    pub fn video_codec_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: impl Into<crate::PrTime>) -> Result<String, Error> {
+        Ok(PrString(call_suite_fn_single!(self, GetVideoCodecString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time.into().ticks())?).into())
     }
 
     /// Retrieves a string representing the "good" state of the source track item, at the specified time.
-    pub fn good_metadata_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: PrTime) -> Result<String, Error> {
-        Ok(PrString(call_suite_fn_single!(self, GetGoodMetadataString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time)?).into())
+    pub fn good_metadata_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: impl Into<crate::PrTime>) -> Result<String, Error> {
+        Ok(PrString(call_suite_fn_single!(self, GetGoodMetadataString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time.into().ticks())?).into())
     }
 
     /// Retrieves a string representing the "sound roll" state of the source track item, at the specified time.
-    pub fn sound_roll_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: PrTime) -> Result<String, Error> {
-        Ok(PrString(call_suite_fn_single!(self, GetSoundRollString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time)?).into())
+    pub fn sound_roll_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: impl Into<crate::PrTime>) -> Result<String, Error> {
+        Ok(PrString(call_suite_fn_single!(self, GetSoundRollString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time.into().ticks())?).into())
     }
 
     /// Retrieves the timebase of the sequence in which the effect is applied.
-    pub fn sequence_time(&self, effect_ref: impl AsPtr<PF_ProgPtr>) -> Result<PrTime, Error> {
-        call_suite_fn_single!(self, GetSequenceTime -> PrTime, effect_ref.as_ptr())
+    pub fn sequence_time(&self, effect_ref: impl AsPtr<PF_ProgPtr>) -> Result<crate::PrTime, Error> {
+        call_suite_fn_single!(self, GetSequenceTime -> pr_sys::PrTime, effect_ref.as_ptr()).map(crate::PrTime)
     }
 
     /// Retrieves the frame of the specified source time.
-    pub fn sound_timecode(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: PrTime) -> Result<i32, Error> {
-        Ok(call_suite_fn_single!(self, GetSoundTimecode -> A_long, effect_ref.as_ptr(), source_track, sequence_time)? as i32)
+    pub fn sound_timecode(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: impl Into<crate::PrTime>) -> Result<i32, Error> {
+        Ok(call_suite_fn_single!(self, GetSoundTimecode -> A_long, effect_ref.as_ptr(), source_track, sequence_time.into().ticks())? as i32)
     }
 
     /// Retrieves the original "ticks per frame" for the specified source track.
-    pub fn original_clip_frame_rate_for_source_track(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32) -> Result<PrTime, Error> {
-        call_suite_fn_single!(self, GetOriginalClipFrameRateForSourceTrack -> PrTime, effect_ref.as_ptr(), source_track)
+    pub fn original_clip_frame_rate_for_source_track(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32) -> Result<crate::PrTime, Error> {
+        call_suite_fn_single!(self, GetOriginalClipFrameRateForSourceTrack -> pr_sys::PrTime, effect_ref.as_ptr(), source_track).map(crate::PrTime)
     }
 
     /// Retrieves the media frame rate for the specified source track.
-    pub fn media_frame_rate_for_source_track(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: PrTime) -> Result<PrTime, Error> {
-        call_suite_fn_single!(self, GetMediaFrameRateForSourceTrack -> PrTime, effect_ref.as_ptr(), source_track, sequence_time)
+    pub fn media_frame_rate_for_source_track(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: impl Into<crate::PrTime>) -> Result<crate::PrTime, Error> {
+        call_suite_fn_single!(self, GetMediaFrameRateForSourceTrack -> pr_sys::PrTime, effect_ref.as_ptr(), source_track, sequence_time.into().ticks()).map(crate::PrTime)
     }
 
     /// Retrieves the start time of the specified layer parameter.
-    pub fn source_track_media_actual_start_time(&self, effect_ref: impl AsPtr<PF_ProgPtr>, layer_param_index: u32, sequence_time: PrTime) -> Result<PrTime, Error> {
-        call_suite_fn_single!(self, GetSourceTrackMediaActualStartTime -> PrTime, effect_ref.as_ptr(), layer_param_index, sequence_time)
+    pub fn source_track_media_actual_start_time(&self, effect_ref: impl AsPtr<PF_ProgPtr>, layer_param_index: u32, sequence_time: impl Into<crate::PrTime>) -> Result<crate::PrTime, Error> {
+        call_suite_fn_single!(self, GetSourceTrackMediaActualStartTime -> pr_sys::PrTime, effect_ref.as_ptr(), layer_param_index, sequence_time.into().ticks()).map(crate::PrTime)
     }
 
     /// Retrieves whether the source track item has been trimmed.
-    pub fn is_source_track_media_trimmed(&self, effect_ref: impl AsPtr<PF_ProgPtr>, layer_param_index: u32, sequence_time: PrTime) -> Result<bool, Error> {
-        call_suite_fn_single!(self, IsSourceTrackMediaTrimmed -> bool, effect_ref.as_ptr(), layer_param_index, sequence_time)
+    pub fn is_source_track_media_trimmed(&self, effect_ref: impl AsPtr<PF_ProgPtr>, layer_param_index: u32, sequence_time: impl Into<crate::PrTime>) -> Result<bool, Error> {
+        call_suite_fn_single!(self, IsSourceTrackMediaTrimmed -> bool, effect_ref.as_ptr(), layer_param_index, sequence_time.into().ticks())
     }
 
     /// Retrieves whether the track item has been trimmed.
-    pub fn is_media_trimmed(&self, effect_ref: impl AsPtr<PF_ProgPtr>, sequence_time: PrTime) -> Result<bool, Error> {
-        call_suite_fn_single!(self, IsMediaTrimmed -> bool, effect_ref.as_ptr(), sequence_time)
+    pub fn is_media_trimmed(&self, effect_ref: impl AsPtr<PF_ProgPtr>, sequence_time: impl Into<crate::PrTime>) -> Result<bool, Error> {
+        call_suite_fn_single!(self, IsMediaTrimmed -> bool, effect_ref.as_ptr(), sequence_time.into().ticks())
     }
 
     /// Retrieves whether, for the specified layer parameter, the track is empty.
-    pub fn is_track_empty(&self, effect_ref: impl AsPtr<PF_ProgPtr>, layer_param_index: u32, sequence_time: PrTime) -> Result<bool, Error> {
-        call_suite_fn_single!(self, IsTrackEmpty -> bool, effect_ref.as_ptr(), layer_param_index, sequence_time)
+    pub fn is_track_empty(&self, effect_ref: impl AsPtr<PF_ProgPtr>, layer_param_index: u32, sequence_time: impl Into<crate::PrTime>) -> Result<bool, Error> {
+        call_suite_fn_single!(self, IsTrackEmpty -> bool, effect_ref.as_ptr(), layer_param_index, sequence_time.into().ticks())
     }
 
     /// Retrieves whether the effect is applied to a track item backed by a synthetic importer.
@@ -263,7 +263,7 @@ impl UtilitySuite {
     /// Retrieves the current media time, including ticks per frame and a formatted string representing that time.
     ///
     /// Returns a tuple containing `(current_media_time, media_ticks_per_frame, media_time_display)`
-    pub fn source_track_current_media_time_info(&self, effect_ref: impl AsPtr<PF_ProgPtr>, layer_param_index: u32, use_sound_timecode_as_start_time: bool, sequence_time: PrTime) -> Result<(PrTime, PrTime, PF_TimeDisplay), Error> {
+    pub fn source_track_current_media_time_info(&self, effect_ref: impl AsPtr<PF_ProgPtr>, layer_param_index: u32, use_sound_timecode_as_start_time: bool, sequence_time: impl Into<crate::PrTime>) -> Result<(crate::PrTime, crate::PrTime, PF_TimeDisplay), Error> {
         let mut current_media_time = 0;
         let mut media_ticks_per_frame = 0;
         let mut media_time_display = 0;
@@ -272,33 +272,33 @@ impl UtilitySuite {
             effect_ref.as_ptr(),
             layer_param_index,
             use_sound_timecode_as_start_time,
-            sequence_time,
+            sequence_time.into().ticks(),
             &mut current_media_time,
             &mut media_ticks_per_frame,
             &mut media_time_display
         )?;
-        Ok((current_media_time, media_ticks_per_frame, media_time_display))
+        Ok((current_media_time.into(), media_ticks_per_frame.into(), media_time_display))
     }
 
     /// Retrieves the zero point (start time) of the sequence in which the effect is applied.
-    pub fn sequence_zero_point(&self, effect_ref: impl AsPtr<PF_ProgPtr>) -> Result<PrTime, Error> {
-        call_suite_fn_single!(self, GetSequenceZeroPoint -> PrTime, effect_ref.as_ptr())
+    pub fn sequence_zero_point(&self, effect_ref: impl AsPtr<PF_ProgPtr>) -> Result<crate::PrTime, Error> {
+        call_suite_fn_single!(self, GetSequenceZeroPoint -> pr_sys::PrTime, effect_ref.as_ptr()).map(crate::PrTime)
     }
 
     /// Retrieves the duration of the clip, at the specified layer index, at inSequenceTime.
-    pub fn source_track_current_clip_duration(&self, effect_ref: impl AsPtr<PF_ProgPtr>, layer_param_index: u32, sequence_time: PrTime) -> Result<PrTime, Error> {
-        call_suite_fn_single!(self, GetSourceTrackCurrentClipDuration -> PrTime, effect_ref.as_ptr(), layer_param_index, sequence_time)
+    pub fn source_track_current_clip_duration(&self, effect_ref: impl AsPtr<PF_ProgPtr>, layer_param_index: u32, sequence_time: impl Into<crate::PrTime>) -> Result<crate::PrTime, Error> {
+        call_suite_fn_single!(self, GetSourceTrackCurrentClipDuration -> pr_sys::PrTime, effect_ref.as_ptr(), layer_param_index, sequence_time.into().ticks()).map(crate::PrTime)
     }
 
     /// Retrieves the duration of the sequence in which the effect is applied.
-    pub fn sequence_duration(&self, effect_ref: impl AsPtr<PF_ProgPtr>) -> Result<PrTime, Error> {
-        call_suite_fn_single!(self, GetSequenceDuration -> PrTime, effect_ref.as_ptr())
+    pub fn sequence_duration(&self, effect_ref: impl AsPtr<PF_ProgPtr>) -> Result<crate::PrTime, Error> {
+        call_suite_fn_single!(self, GetSequenceDuration -> pr_sys::PrTime, effect_ref.as_ptr()).map(crate::PrTime)
     }
 
     /// Retrieve a string representing the dimensions of the track item to which the effect is applied.
     /// It's formatted as a "width x height".
     /// Set `source_track` to -1 to query the top-most clip at `sequence_time` (only if effect is on an adjustment layer)
-    pub fn video_resolution_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: PrTime) -> Result<String, Error> {
-        Ok(PrString(call_suite_fn_single!(self, GetVideoResolutionString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time)?).into())
+    pub fn video_resolution_string(&self, effect_ref: impl AsPtr<PF_ProgPtr>, source_track: i32, sequence_time: impl Into<crate::PrTime>) -> Result<String, Error> {
+        Ok(PrString(call_suite_fn_single!(self, GetVideoResolutionString -> PrSDKString, effect_ref.as_ptr(), source_track, sequence_time.into().ticks())?).into())
     }
 }
@@ -0,0 +1,202 @@
+use crate::*;
+use serde::{ Deserialize, Serialize };
+use std::ops::{ Add, AddAssign, Div, Mul, Neg, Rem, Sub, SubAssign };
+use std::str::FromStr;
+
+/// A point in time or a duration, in Premiere ticks.
+///
+/// All Premiere APIs work with a signed 64-bit tick count. There are [`PrTime::TICKS_PER_SECOND`] ticks in a second,
+/// which is divisible by all common video frame rates and audio sample rates, so frame and sample times are exact.
+///
+/// Arithmetic on ticks is exact. Conversions to rationals, like After Effects' `Time`, are lossless or fail.
+///
+/// Like `i64`, the operators panic on overflow in debug builds and wrap around in release builds, and division by zero panics.
+/// Use the `checked_*` and `saturating_*` methods for times which can be out of range, like `i64::MIN` which Premiere uses as "no time".
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[repr(transparent)]
+pub struct PrTime(pub i64);
+
+impl PrTime {
+    /// Number of ticks in a second. This is the value returned by [`suites::Time::ticks_per_second()`](crate::suites::Time::ticks_per_second).
+    pub const TICKS_PER_SECOND: i64 = 254016000000;
+    pub const ZERO: Self = Self(0);
+
+    pub const fn from_ticks(ticks: i64) -> Self {
+        Self(ticks)
+    }
+    pub const fn ticks(self) -> i64 {
+        self.0
+    }
+
+    /// Time of the given number of seconds. Rounded to the nearest tick.
+    pub fn from_seconds_f64(seconds: f64) -> Self {
+        Self((seconds * Self::TICKS_PER_SECOND as f64).round() as i64)
+    }
+    pub fn as_seconds_f64(self) -> f64 {
+        self.0 as f64 / Self::TICKS_PER_SECOND as f64
+    }
+
+    /// Time of `value / scale` seconds. Returns `None` if it can't be represented in ticks exactly, or if `scale` is zero.
+    pub fn from_rational(value: i64, scale: i64) -> Option<Self> {
+        if scale == 0 {
+            return None;
+        }
+        let ticks = value as i128 * Self::TICKS_PER_SECOND as i128;
+        if ticks % scale as i128 != 0 {
+            return None;
+        }
+        i64::try_from(ticks / scale as i128).ok().map(Self)
+    }
+
+    /// This time as `(value, scale)` seconds, reduced to the smallest scale.
+    pub fn to_rational(self) -> (i64, i64) {
+        let gcd = gcd(self.0.unsigned_abs(), Self::TICKS_PER_SECOND as u64) as i64;
+        (self.0 / gcd, Self::TICKS_PER_SECOND / gcd)
+    }
+
+    /// Time of the start of `frame`. Saturates on overflow.
+    /// * `ticks_per_frame` - Duration of a frame, from [`suites::Time::ticks_per_video_frame()`](crate::suites::Time::ticks_per_video_frame) or [`suites::SequenceInfo::frame_rate()`](crate::suites::SequenceInfo::frame_rate).
+    pub fn from_frames(frame: i64, ticks_per_frame: impl Into<PrTime>) -> Self {
+        Self(frame.saturating_mul(ticks_per_frame.into().0))
+    }
+    /// The frame which contains this time. Rounds towards negative infinity. Returns `0` if `ticks_per_frame` is zero.
+    pub fn frames(self, ticks_per_frame: impl Into<PrTime>) -> i64 {
+        self.0.checked_div_euclid(ticks_per_frame.into().0).unwrap_or(0)
+    }
+
+    /// Time of the start of `sample`. Saturates on overflow.
+    /// * `ticks_per_sample` - Duration of a sample, from [`suites::Time::ticks_per_audio_sample()`](crate::suites::Time::ticks_per_audio_sample).
+    pub fn from_samples(sample: i64, ticks_per_sample: impl Into<PrTime>) -> Self {
+        Self(sample.saturating_mul(ticks_per_sample.into().0))
+    }
+    /// The audio sample which contains this time. Rounds towards negative infinity. Returns `0` if `ticks_per_sample` is zero.
+    pub fn samples(self, ticks_per_sample: impl Into<PrTime>) -> i64 {
+        self.0.checked_div_euclid(ticks_per_sample.into().0).unwrap_or(0)
+    }
+
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+    pub fn checked_mul(self, rhs: i64) -> Option<Self> {
+        self.0.checked_mul(rhs).map(Self)
+    }
+    pub fn checked_neg(self) -> Option<Self> {
+        self.0.checked_neg().map(Self)
+    }
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        Self(self.0.saturating_add(rhs.0))
+    }
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+    pub fn saturating_mul(self, rhs: i64) -> Self {
+        Self(self.0.saturating_mul(rhs))
+    }
+    pub fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+}
+
+fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a.max(1)
+}
+
+impl From<i64> for PrTime {
+    fn from(ticks: i64) -> Self {
+        Self(ticks)
+    }
+}
+impl From<PrTime> for i64 {
+    fn from(time: PrTime) -> Self {
+        time.0
+    }
+}
+impl FromStr for PrTime {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.trim().parse().map(Self).map_err(|_| Error::InvalidParms)
+    }
+}
+impl std::fmt::Display for PrTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Add for PrTime {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self { Self(self.0 + rhs.0) }
+}
+impl Sub for PrTime {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self { Self(self.0 - rhs.0) }
+}
+impl AddAssign for PrTime {
+    fn add_assign(&mut self, rhs: Self) { self.0 += rhs.0; }
+}
+impl SubAssign for PrTime {
+    fn sub_assign(&mut self, rhs: Self) { self.0 -= rhs.0; }
+}
+impl Neg for PrTime {
+    type Output = Self;
+    fn neg(self) -> Self { Self(-self.0) }
+}
+impl Mul<i64> for PrTime {
+    type Output = Self;
+    fn mul(self, rhs: i64) -> Self { Self(self.0 * rhs) }
+}
+impl Div<i64> for PrTime {
+    type Output = Self;
+    fn div(self, rhs: i64) -> Self { Self(self.0 / rhs) }
+}
+/// How many times `rhs` fits in `self`.
+impl Div for PrTime {
+    type Output = i64;
+    fn div(self, rhs: Self) -> i64 { self.0 / rhs.0 }
+}
+impl Rem for PrTime {
+    type Output = Self;
+    fn rem(self, rhs: Self) -> Self { Self(self.0 % rhs.0) }
+}
+
+#[cfg(feature = "after-effects")]
+impl TryFrom<after_effects::Time> for PrTime {
+    type Error = Error;
+    /// Fails with [`Error::InvalidParms`] if the time can't be represented in ticks exactly.
+    fn try_from(time: after_effects::Time) -> Result<Self, Error> {
+        Self::from_rational(time.value as i64, time.scale as i64).ok_or(Error::InvalidParms)
+    }
+}
+#[cfg(feature = "after-effects")]
+impl TryFrom<PrTime> for after_effects::Time {
+    type Error = Error;
+    /// Fails with [`Error::InvalidParms`] if the reduced value or scale don't fit in `Time`.
+    fn try_from(time: PrTime) -> Result<Self, Error> {
+        let (value, scale) = time.to_rational();
+        Ok(after_effects::Time {
+            value: value.try_into().map_err(|_| Error::InvalidParms)?,
+            scale: scale.try_into().map_err(|_| Error::InvalidParms)?,
+        })
+    }
+}
+
+#[test]
+fn test_rational() {
+    let frame = PrTime::from_rational(1001, 30000).unwrap();
+    assert_eq!(frame.ticks(), 8475667200);
+    assert_eq!(frame.to_rational(), (1001, 30000));
+    assert_eq!(PrTime::from_frames(3, frame).frames(frame), 3);
+    assert_eq!((PrTime::from_frames(3, frame) - PrTime(1)).frames(frame), 2);
+    assert_eq!(PrTime::from_rational(1, 11), None);
+    assert_eq!(PrTime::ZERO.to_rational(), (0, 1));
+    assert_eq!(PrTime(100).frames(PrTime::ZERO), 0);
+    assert_eq!(PrTime(i64::MIN).checked_sub(frame), None);
+    assert_eq!(PrTime(i64::MAX).saturating_add(frame), PrTime(i64::MAX));
+    assert_eq!(PrTime::from_frames(i64::MAX, frame), PrTime(i64::MAX));
+}
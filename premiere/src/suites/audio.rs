@@ -0,0 +1,102 @@
+use crate::*;
+
+define_suite!(
+    /// Audio buffer conversion routines.
+    ///
+    /// Premiere works with uninterleaved 32-bit float audio, one buffer per channel. These calls convert between that layout
+    /// and interleaved integer audio of a specific sample size.
+    AudioSuite,
+    PrSDKAudioSuite,
+    kPrSDKAudioSuite,
+    kPrSDKAudioSuiteVersion
+);
+
+impl AudioSuite {
+    /// Acquire this suite from the host. Returns error if the suite is not available.
+    /// Suite is released on drop.
+    pub fn new() -> Result<Self, Error> {
+        crate::Suite::new()
+    }
+
+    /// Convert an array of float buffers into interleaved 8-bit integer audio.
+    /// * `source` - One float buffer per channel. All buffers must have the same length.
+    /// * `destination` - Interleaved output, must be at least `channels * frames` samples long.
+    pub fn convert_and_interleave_to_8bit(&self, source: &[&[f32]], destination: &mut [i8]) -> Result<(), Error> {
+        let (mut channels, frames) = Self::source_ptrs(source, destination.len(), 1)?;
+        call_suite_fn!(self, ConvertAndInterleaveTo8BitInteger, channels.as_mut_ptr(), destination.as_mut_ptr() as *mut _, channels.len() as _, frames as _)
+    }
+
+    /// Convert an array of float buffers into interleaved 16-bit integer audio.
+    /// * `source` - One float buffer per channel. All buffers must have the same length.
+    /// * `destination` - Interleaved output, must be at least `channels * frames` samples long.
+    pub fn convert_and_interleave_to_16bit(&self, source: &[&[f32]], destination: &mut [i16]) -> Result<(), Error> {
+        let (mut channels, frames) = Self::source_ptrs(source, destination.len(), 1)?;
+        call_suite_fn!(self, ConvertAndInterleaveTo16BitInteger, channels.as_mut_ptr(), destination.as_mut_ptr(), channels.len() as _, frames as _)
+    }
+
+    /// Convert an array of float buffers into interleaved, packed 24-bit integer audio.
+    /// * `source` - One float buffer per channel. All buffers must have the same length.
+    /// * `destination` - Interleaved output, 3 bytes per sample. Must be at least `channels * frames * 3` bytes long.
+    pub fn convert_and_interleave_to_24bit(&self, source: &[&[f32]], destination: &mut [u8]) -> Result<(), Error> {
+        let (mut channels, frames) = Self::source_ptrs(source, destination.len(), 3)?;
+        call_suite_fn!(self, ConvertAndInterleaveTo24BitInteger, channels.as_mut_ptr(), destination.as_mut_ptr() as *mut _, channels.len() as _, frames as _)
+    }
+
+    /// Convert an array of float buffers into interleaved 32-bit integer audio.
+    /// * `source` - One float buffer per channel. All buffers must have the same length.
+    /// * `destination` - Interleaved output, must be at least `channels * frames` samples long.
+    pub fn convert_and_interleave_to_32bit(&self, source: &[&[f32]], destination: &mut [i32]) -> Result<(), Error> {
+        let (mut channels, frames) = Self::source_ptrs(source, destination.len(), 1)?;
+        call_suite_fn!(self, ConvertAndInterleaveTo32BitInteger, channels.as_mut_ptr(), destination.as_mut_ptr(), channels.len() as _, frames as _)
+    }
+
+    /// Convert interleaved 8-bit integer audio into an array of float buffers.
+    /// * `source` - Interleaved input, must be at least `channels * frames` samples long.
+    /// * `destination` - One float buffer per channel. All buffers must have the same length.
+    pub fn uninterleave_and_convert_from_8bit(&self, source: &[i8], destination: &mut [&mut [f32]]) -> Result<(), Error> {
+        let (mut channels, frames) = Self::destination_ptrs(destination, source.len(), 1)?;
+        call_suite_fn!(self, UninterleaveAndConvertFrom8BitInteger, source.as_ptr() as *mut _, channels.as_mut_ptr(), channels.len() as _, frames as _)
+    }
+
+    /// Convert interleaved 16-bit integer audio into an array of float buffers.
+    /// * `source` - Interleaved input, must be at least `channels * frames` samples long.
+    /// * `destination` - One float buffer per channel. All buffers must have the same length.
+    pub fn uninterleave_and_convert_from_16bit(&self, source: &[i16], destination: &mut [&mut [f32]]) -> Result<(), Error> {
+        let (mut channels, frames) = Self::destination_ptrs(destination, source.len(), 1)?;
+        call_suite_fn!(self, UninterleaveAndConvertFrom16BitInteger, source.as_ptr() as *mut _, channels.as_mut_ptr(), channels.len() as _, frames as _)
+    }
+
+    /// Convert interleaved, packed 24-bit integer audio into an array of float buffers.
+    /// * `source` - Interleaved input, 3 bytes per sample. Must be at least `channels * frames * 3` bytes long.
+    /// * `destination` - One float buffer per channel. All buffers must have the same length.
+    pub fn uninterleave_and_convert_from_24bit(&self, source: &[u8], destination: &mut [&mut [f32]]) -> Result<(), Error> {
+        let (mut channels, frames) = Self::destination_ptrs(destination, source.len(), 3)?;
+        call_suite_fn!(self, UninterleaveAndConvertFrom24BitInteger, source.as_ptr() as *mut _, channels.as_mut_ptr(), channels.len() as _, frames as _)
+    }
+
+    /// Convert interleaved 32-bit integer audio into an array of float buffers.
+    /// * `source` - Interleaved input, must be at least `channels * frames` samples long.
+    /// * `destination` - One float buffer per channel. All buffers must have the same length.
+    pub fn uninterleave_and_convert_from_32bit(&self, source: &[i32], destination: &mut [&mut [f32]]) -> Result<(), Error> {
+        let (mut channels, frames) = Self::destination_ptrs(destination, source.len(), 1)?;
+        call_suite_fn!(self, UninterleaveAndConvertFrom32BitInteger, source.as_ptr() as *mut _, channels.as_mut_ptr(), channels.len() as _, frames as _)
+    }
+
+    fn frame_count(lengths: impl Iterator<Item = usize>, channels: usize, interleaved_len: usize, bytes_per_sample: usize) -> Result<usize, Error> {
+        let mut lengths = lengths.peekable();
+        let frames = lengths.peek().copied().ok_or(Error::InvalidParms)?;
+        if lengths.any(|x| x != frames) || interleaved_len < channels * frames * bytes_per_sample {
+            return Err(Error::InvalidParms);
+        }
+        Ok(frames)
+    }
+    fn source_ptrs(source: &[&[f32]], interleaved_len: usize, bytes_per_sample: usize) -> Result<(Vec<*mut f32>, usize), Error> {
+        let frames = Self::frame_count(source.iter().map(|x| x.len()), source.len(), interleaved_len, bytes_per_sample)?;
+        // The host only reads from the source buffers
+        Ok((source.iter().map(|x| x.as_ptr() as *mut f32).collect(), frames))
+    }
+    fn destination_ptrs(destination: &mut [&mut [f32]], interleaved_len: usize, bytes_per_sample: usize) -> Result<(Vec<*mut f32>, usize), Error> {
+        let frames = Self::frame_count(destination.iter().map(|x| x.len()), destination.len(), interleaved_len, bytes_per_sample)?;
+        Ok((destination.iter_mut().map(|x| x.as_mut_ptr()).collect(), frames))
+    }
+}
@@ -0,0 +1,121 @@
+use crate::*;
+
+define_suite!(
+    /// Calls to get frames directly from the media of a clip, bypassing the sequence render.
+    ///
+    /// Use [`supports_clip_render_suite()`](Self::supports_clip_render_suite) to check if the clip can be used with this suite.
+    /// Frames are requested with [`find_frame()`](Self::find_frame), optionally after starting an asynchronous read with
+    /// [`initiate_async_read()`](Self::initiate_async_read).
+    ///
+    /// Version 2 adds [`clip_field_type()`](Self::clip_field_type).
+    ClipRenderSuite,
+    PrSDKClipRenderSuite,
+    kPrSDKClipRenderSuite,
+    kPrSDKClipRenderSuiteVersion
+);
+
+/// The format of a frame requested from [`ClipRenderSuite`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClipFrameFormat {
+    pub pixel_format: PixelFormat,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl From<ClipFrameFormat> for pr_sys::ClipFrameFormat {
+    fn from(f: ClipFrameFormat) -> Self {
+        Self {
+            inPixelFormat: f.pixel_format.into(),
+            inWidth: f.width,
+            inHeight: f.height,
+        }
+    }
+}
+
+impl ClipRenderSuite {
+    /// Acquire this suite from the host. Returns error if the suite is not available.
+    /// Suite is released on drop.
+    pub fn new() -> Result<Self, Error> {
+        crate::Suite::new()
+    }
+
+    /// Does this clip support the clip render suite functions?
+    /// * `clip_id` - The ClipID of the clip.
+    ///
+    /// Returns a tuple containing:
+    /// * `supported` - If true, the clip supports the ClipRenderSuite.
+    /// * `async_io_supported` - If true, the clip supports asynchronous IO. If false, the clip does not directly support asynchronous IO,
+    ///   however it is still legal to call [`initiate_async_read()`](Self::initiate_async_read).
+    pub fn supports_clip_render_suite(&self, clip_id: pr_sys::PrClipID) -> Result<(bool, bool), Error> {
+        let (supported, async_io_supported) = call_suite_fn_double!(self, SupportsClipRenderSuite -> pr_sys::prBool, pr_sys::prBool, clip_id)?;
+        Ok((supported != 0, async_io_supported != 0))
+    }
+
+    /// Returns the pixel formats supported by the clip, in order of preference.
+    /// * `clip_id` - The ClipID of the clip.
+    pub fn pixel_formats(&self, clip_id: pr_sys::PrClipID) -> Result<Vec<PixelFormat>, Error> {
+        let count = call_suite_fn_single!(self, GetNumPixelFormats -> i32, clip_id)?;
+        (0..count)
+            .map(|i| Ok(call_suite_fn_single!(self, GetPixelFormat -> pr_sys::PrPixelFormat, clip_id, i)?.into()))
+            .collect()
+    }
+
+    /// Returns the custom pixel formats supported by the clip. These are opaque to the host, so they are returned as raw values.
+    /// * `clip_id` - The ClipID of the clip.
+    pub fn custom_pixel_formats(&self, clip_id: pr_sys::PrClipID) -> Result<Vec<pr_sys::PrPixelFormat>, Error> {
+        let count = call_suite_fn_single!(self, GetNumCustomPixelFormats -> i32, clip_id)?;
+        (0..count)
+            .map(|i| call_suite_fn_single!(self, GetCustomPixelFormat -> pr_sys::PrPixelFormat, clip_id, i))
+            .collect()
+    }
+
+    /// Returns the preferred frame sizes for a pixel format as `(width, height)`. The first entry is the native size of the media.
+    /// * `clip_id` - The ClipID of the clip.
+    /// * `pixel_format` - One of the formats returned from [`pixel_formats()`](Self::pixel_formats).
+    pub fn preferred_frame_sizes(&self, clip_id: pr_sys::PrClipID, pixel_format: PixelFormat) -> Result<Vec<(i32, i32)>, Error> {
+        let count = call_suite_fn_single!(self, GetNumPreferredFrameSizes -> i32, clip_id, pixel_format.into())?;
+        (0..count)
+            .map(|i| call_suite_fn_double!(self, GetPreferredFrameSize -> i32, i32, clip_id, pixel_format.into(), i))
+            .collect()
+    }
+
+    /// Starts reading a frame asynchronously, so that a later [`find_frame()`](Self::find_frame) returns sooner.
+    /// * `clip_id` - The ClipID of the clip.
+    /// * `time` - The time of the frame in ticks.
+    /// * `format` - The format of the frame.
+    pub fn initiate_async_read(&self, clip_id: pr_sys::PrClipID, time: impl Into<PrTime>, format: ClipFrameFormat) -> Result<(), Error> {
+        let time = time.into().ticks();
+        let mut format: pr_sys::ClipFrameFormat = format.into();
+        call_suite_fn!(self, InitiateAsyncRead, clip_id, &time, &mut format)
+    }
+
+    /// Cancels a read started with [`initiate_async_read()`](Self::initiate_async_read).
+    pub fn cancel_async_read(&self, clip_id: pr_sys::PrClipID, time: impl Into<PrTime>, format: ClipFrameFormat) -> Result<(), Error> {
+        let time = time.into().ticks();
+        let mut format: pr_sys::ClipFrameFormat = format.into();
+        call_suite_fn!(self, CancelAsyncRead, clip_id, &time, &mut format)
+    }
+
+    /// Get a frame of the clip.
+    /// * `clip_id` - The ClipID of the clip.
+    /// * `time` - The time of the frame in ticks.
+    /// * `formats` - The acceptable formats, in order of preference.
+    /// * `synchronous` - If true, the frame is read if it's not available yet. If false, only already read frames are returned.
+    ///
+    /// Returns the frame, which is disposed on drop, or [`Error::RenderedFrameNotFound`] if the frame is not available.
+    pub fn find_frame(&self, clip_id: pr_sys::PrClipID, time: impl Into<PrTime>, formats: &[ClipFrameFormat], synchronous: bool) -> Result<OwnedPPix, Error> {
+        let time = time.into().ticks();
+        let mut formats: Vec<pr_sys::ClipFrameFormat> = formats.iter().map(|&x| x.into()).collect();
+        let frame = call_suite_fn_single!(self, FindFrame -> pr_sys::PPixHand, clip_id, &time, formats.as_mut_ptr(), formats.len() as i32, synchronous)?;
+        if frame.is_null() {
+            return Err(Error::RenderedFrameNotFound);
+        }
+        OwnedPPix::from_raw(frame)
+    }
+
+    /// What is the field type of this clip?
+    /// * `clip_id` - The ClipID of the clip.
+    pub fn clip_field_type(&self, clip_id: pr_sys::PrClipID) -> Result<pr_sys::prFieldType, Error> {
+        call_suite_fn_single!(self, GetClipFieldType -> pr_sys::prFieldType, clip_id)
+    }
+}
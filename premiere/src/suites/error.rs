@@ -0,0 +1,45 @@
+use crate::*;
+
+define_suite!(
+    /// Report errors, warnings and informational messages to the user.
+    ///
+    /// Events show up in the Events panel of Premiere.
+    /// See also [`EventLogger`](crate::utils::EventLogger) to forward [`log`] records from plugin code.
+    ErrorSuite,
+    PrSDKErrorSuite3,
+    kPrSDKErrorSuite,
+    kPrSDKErrorSuiteVersion3
+);
+
+define_enum! {
+    pr_sys::csSDK_uint32,
+    EventType {
+        Informational = pr_sys::PrSDKErrorSuite3_kEventTypeInformational,
+        Warning       = pr_sys::PrSDKErrorSuite3_kEventTypeWarning,
+        Error         = pr_sys::PrSDKErrorSuite3_kEventTypeError,
+    }
+}
+
+impl ErrorSuite {
+    /// Acquire this suite from the host. Returns error if the suite is not available.
+    /// Suite is released on drop.
+    pub fn new() -> Result<Self, Error> {
+        crate::Suite::new()
+    }
+
+    /// Add an event to the Events panel.
+    /// * `event_type` - The severity of the event.
+    /// * `title` - Short title of the event.
+    /// * `description` - Longer description, shown when the event is selected.
+    pub fn set_event_string(&self, event_type: EventType, title: &str, description: &str) -> Result<(), Error> {
+        let mut title: Vec<u16> = title.encode_utf16().chain(std::iter::once(0)).collect();
+        let mut description: Vec<u16> = description.encode_utf16().chain(std::iter::once(0)).collect();
+        call_suite_fn!(self, SetEventStringUnicode, event_type.into(), title.as_mut_ptr(), description.as_mut_ptr())
+    }
+
+    /// Report an [`Error`] to the user, with its description as the event text.
+    pub fn report_error(&self, title: &str, error: Error) -> Result<(), Error> {
+        let description: &'static str = error.into();
+        self.set_event_string(EventType::Error, title, description)
+    }
+}
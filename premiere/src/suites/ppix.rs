@@ -139,3 +139,69 @@ impl PPix2Suite {
         call_suite_fn_single!(self, GetFieldOrder -> pr_sys::prFieldType, ppix_handle)
     }
 }
+
+/// A PPix owned by the plugin, such as a frame returned from [`SequenceRenderSuite`](crate::suites::SequenceRender) or [`ClipRenderSuite`](crate::suites::ClipRender).
+///
+/// The frame is disposed with [`PPixSuite::dispose()`] on drop.
+pub struct OwnedPPix {
+    handle: pr_sys::PPixHand,
+    suite: PPixSuite,
+}
+
+impl OwnedPPix {
+    /// Take ownership of `handle`. Returns error if the PPix suite is not available.
+    pub fn from_raw(handle: pr_sys::PPixHand) -> Result<Self, Error> {
+        if handle.is_null() {
+            return Err(Error::InvalidParms);
+        }
+        Ok(Self {
+            handle,
+            suite: PPixSuite::new()?,
+        })
+    }
+    /// Release ownership of the handle without disposing it.
+    pub fn into_raw(self) -> pr_sys::PPixHand {
+        let this = std::mem::ManuallyDrop::new(self);
+        // Release the suite, but keep the handle alive
+        drop(unsafe { std::ptr::read(&this.suite) });
+        this.handle
+    }
+    pub fn as_ptr(&self) -> pr_sys::PPixHand {
+        self.handle
+    }
+
+    /// See [`PPixSuite::pixels()`]
+    pub fn pixels(&self, requested_access: PPixBufferAccess) -> Result<*mut std::ffi::c_char, Error> {
+        self.suite.pixels(self.handle, requested_access)
+    }
+    /// See [`PPixSuite::bounds()`]
+    pub fn bounds(&self) -> Result<pr_sys::prRect, Error> {
+        self.suite.bounds(self.handle)
+    }
+    /// See [`PPixSuite::row_bytes()`]
+    pub fn row_bytes(&self) -> Result<i32, Error> {
+        self.suite.row_bytes(self.handle)
+    }
+    /// See [`PPixSuite::pixel_aspect_ratio()`]
+    pub fn pixel_aspect_ratio(&self) -> Result<(u32, u32), Error> {
+        self.suite.pixel_aspect_ratio(self.handle)
+    }
+    /// See [`PPixSuite::pixel_format()`]
+    pub fn pixel_format(&self) -> Result<PixelFormat, Error> {
+        self.suite.pixel_format(self.handle)
+    }
+}
+
+impl Drop for OwnedPPix {
+    fn drop(&mut self) {
+        if let Err(e) = self.suite.dispose(self.handle) {
+            log::error!("Failed to dispose PPix: {e:?}");
+        }
+    }
+}
+
+impl AsPtr<pr_sys::PPixHand> for OwnedPPix {
+    fn as_ptr(&self) -> pr_sys::PPixHand {
+        self.handle
+    }
+}
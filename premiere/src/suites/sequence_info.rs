@@ -48,8 +48,8 @@ impl SequenceInfoSuite {
     /// * `timeline_id` - the timeline instance data
     ///
     /// Returns the framerate in ticks.
-    pub fn frame_rate(&self, timeline_id: pr_sys::PrTimelineID) -> Result<pr_sys::PrTime, Error> {
-        call_suite_fn_single!(self, GetFrameRate -> pr_sys::PrTime, timeline_id)
+    pub fn frame_rate(&self, timeline_id: pr_sys::PrTimelineID) -> Result<crate::PrTime, Error> {
+        call_suite_fn_single!(self, GetFrameRate -> pr_sys::PrTime, timeline_id).map(crate::PrTime)
     }
     /// Get the field type of the sequence.
     /// * `timeline_id` - the timeline instance data
@@ -62,8 +62,8 @@ impl SequenceInfoSuite {
     /// * `timeline_id` - the timeline instance data
     ///
     /// Returns start time of the sequence.
-    pub fn zero_point(&self, timeline_id: pr_sys::PrTimelineID) -> Result<pr_sys::PrTime, Error> {
-        call_suite_fn_single!(self, GetZeroPoint -> pr_sys::PrTime, timeline_id)
+    pub fn zero_point(&self, timeline_id: pr_sys::PrTimelineID) -> Result<crate::PrTime, Error> {
+        call_suite_fn_single!(self, GetZeroPoint -> pr_sys::PrTime, timeline_id).map(crate::PrTime)
     }
     /// Returns if the sequence timecode is drop or non drop.
     /// * `timeline_id` - the timeline instance data
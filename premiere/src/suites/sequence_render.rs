@@ -0,0 +1,450 @@
+use crate::*;
+use pr_sys::{ SequenceRender_GetFrameReturnRec, SequenceRender_ParamsRec, SequenceRender_ParamsRecExt };
+
+define_suite!(
+    /// Calls to render frames of a sequence, synchronously or asynchronously.
+    ///
+    /// Effects that need frames from other points in time, such as temporal denoise or frame blending, can create a video renderer
+    /// for the current timeline and request any frame from it. See [`VideoRenderer`] for a safe wrapper that releases the renderer on drop
+    /// and delivers asynchronous frames to a closure.
+    ///
+    /// Version 2, new in CS4, adds [`prefetch_media_with_render_parameters()`](Self::prefetch_media_with_render_parameters),
+    /// [`cancel_all_outstanding_media_prefetches()`](Self::cancel_all_outstanding_media_prefetches) and [`is_prefetched_media_ready()`](Self::is_prefetched_media_ready).
+    ///
+    /// Version 3, new in CS5, adds [`make_video_renderer_for_timeline()`](Self::make_video_renderer_for_timeline) and [`release_video_renderer_for_timeline()`](Self::release_video_renderer_for_timeline).
+    ///
+    /// Version 4, new in CS5.0.3, adds [`render_video_frame_and_conform_to_pixel_format()`](Self::render_video_frame_and_conform_to_pixel_format).
+    ///
+    /// Version 6 adds the color managed variants, used when [`SequenceRenderParams::color_space`] is set.
+    SequenceRenderSuite,
+    PrSDKSequenceRenderSuite,
+    kPrSDKSequenceRenderSuite,
+    kPrSDKSequenceRenderSuiteVersion
+);
+
+bitflags::bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct RenderCacheType: i32 {
+        const NONE          = 0x0;
+        const IMAGE_BUFFERS = 0x1;
+        const FRAMES        = 0x2;
+    }
+}
+
+/// Parameters for a frame render request.
+#[derive(Clone, Debug)]
+pub struct SequenceRenderParams {
+    /// Pixel formats in order of preference. [`PixelFormat::Bgra4444_8u`] is appended if it's not the last entry, as required by the host.
+    pub pixel_formats: Vec<PixelFormat>,
+    pub width: i32,
+    pub height: i32,
+    /// Numerator and denominator of the pixel aspect ratio.
+    pub pixel_aspect_ratio: (i32, i32),
+    pub quality: RenderQuality,
+    pub field_type: pr_sys::prFieldType,
+    pub deinterlace: bool,
+    pub deinterlace_quality: RenderQuality,
+    pub composite_on_black: bool,
+    /// If set, the color managed render calls are used and the frame is rendered in this color space.
+    pub color_space: Option<pr_sys::PrSDKColorSpaceID>,
+}
+
+impl SequenceRenderParams {
+    pub fn new(pixel_formats: &[PixelFormat], width: i32, height: i32) -> Self {
+        Self {
+            pixel_formats: pixel_formats.to_vec(),
+            width,
+            height,
+            pixel_aspect_ratio: (1, 1),
+            quality: RenderQuality::High,
+            field_type: 0, // prFieldsNone, progressive
+            deinterlace: false,
+            deinterlace_quality: RenderQuality::High,
+            composite_on_black: false,
+            color_space: None,
+        }
+    }
+
+    /// Request frames with the same size, pixel aspect ratio, quality and field type as the current render.
+    pub fn from_render_params(render_params: &RenderParams, pixel_formats: &[PixelFormat]) -> Self {
+        let (par_num, par_den) = render_params.render_pixel_aspect_ratio();
+        Self {
+            pixel_aspect_ratio: (par_num as i32, par_den as i32),
+            quality: render_params.quality(),
+            field_type: render_params.render_field_type(),
+            deinterlace_quality: render_params.quality(),
+            ..Self::new(pixel_formats, render_params.render_width() as i32, render_params.render_height() as i32)
+        }
+    }
+
+    fn raw_pixel_formats(&self) -> Vec<pr_sys::PrPixelFormat> {
+        let mut formats: Vec<pr_sys::PrPixelFormat> = self.pixel_formats.iter().map(|&x| x.into()).collect();
+        if self.pixel_formats.last() != Some(&PixelFormat::Bgra4444_8u) {
+            formats.push(PixelFormat::Bgra4444_8u.into());
+        }
+        formats
+    }
+
+    /// `pixel_formats` must outlive the returned struct.
+    fn as_raw(&self, pixel_formats: &[pr_sys::PrPixelFormat]) -> SequenceRender_ParamsRec {
+        SequenceRender_ParamsRec {
+            inRequestedPixelFormatArray:      pixel_formats.as_ptr(),
+            inRequestedPixelFormatArrayCount: pixel_formats.len() as _,
+            inWidth:                          self.width,
+            inHeight:                         self.height,
+            inPixelAspectRatioNumerator:      self.pixel_aspect_ratio.0,
+            inPixelAspectRatioDenominator:    self.pixel_aspect_ratio.1,
+            inRenderQuality:                  self.quality.into(),
+            inFieldType:                      self.field_type,
+            inDeinterlace:                    self.deinterlace as _,
+            inDeinterlaceQuality:             self.deinterlace_quality.into(),
+            inCompositeOnBlack:               self.composite_on_black as _,
+        }
+    }
+
+    /// `pixel_formats` must outlive the returned struct.
+    fn as_raw_ext(&self, pixel_formats: &[pr_sys::PrPixelFormat], color_space: pr_sys::PrSDKColorSpaceID) -> SequenceRender_ParamsRecExt {
+        let p = self.as_raw(pixel_formats);
+        SequenceRender_ParamsRecExt {
+            inRequestedPixelFormatArray:      p.inRequestedPixelFormatArray,
+            inRequestedPixelFormatArrayCount: p.inRequestedPixelFormatArrayCount,
+            inWidth:                          p.inWidth,
+            inHeight:                         p.inHeight,
+            inPixelAspectRatioNumerator:      p.inPixelAspectRatioNumerator,
+            inPixelAspectRatioDenominator:    p.inPixelAspectRatioDenominator,
+            inRenderQuality:                  p.inRenderQuality,
+            inFieldType:                      p.inFieldType,
+            inDeinterlace:                    p.inDeinterlace,
+            inDeinterlaceQuality:             p.inDeinterlaceQuality,
+            inCompositeOnBlack:               p.inCompositeOnBlack,
+            inPrSDKColorSpaceID:              color_space,
+        }
+    }
+}
+
+/// A frame returned from a render request.
+pub struct RenderedFrame {
+    pub frame: OwnedPPix,
+    /// The number of subsequent frames which are identical to this one.
+    pub repeat_count: i32,
+    /// Whether the frame is on a marker.
+    pub on_marker: bool,
+}
+
+impl RenderedFrame {
+    fn from_raw(ret: &SequenceRender_GetFrameReturnRec) -> Result<Self, Error> {
+        let ret = *ret;
+        Ok(Self {
+            frame: OwnedPPix::from_raw(ret.outFrame)?,
+            repeat_count: ret.repeatCount,
+            on_marker: ret.onMarker != 0,
+        })
+    }
+}
+
+/// Result of an asynchronous render request, passed to the closure set with [`VideoRenderer::set_async_completion()`].
+pub struct AsyncRenderedFrame {
+    pub video_render_id: u32,
+    /// The time of the requested frame.
+    pub time: PrTime,
+    /// The tag passed to [`VideoRenderer::queue_async_frame_render()`].
+    pub tag: usize,
+    /// The rendered frame, or the error returned by the host.
+    pub frame: Result<RenderedFrame, Error>,
+}
+
+impl SequenceRenderSuite {
+    /// Acquire this suite from the host. Returns error if the suite is not available.
+    /// Suite is released on drop.
+    pub fn new() -> Result<Self, Error> {
+        crate::Suite::new()
+    }
+
+    /// Create a video renderer, for use by a plugin.
+    /// * `plugin_id` - The ID of the plugin.
+    /// * `frame_rate` - The frame rate, as the duration of one frame.
+    ///
+    /// Returns the video render ID, which must be released with [`release_video_renderer()`](Self::release_video_renderer).
+    pub fn make_video_renderer(&self, plugin_id: u32, frame_rate: impl Into<PrTime>) -> Result<u32, Error> {
+        let mut id = 0;
+        call_suite_fn!(self, MakeVideoRenderer, plugin_id, &mut id, frame_rate.into().ticks())?;
+        Ok(id)
+    }
+    /// Release a video renderer created with [`make_video_renderer()`](Self::make_video_renderer).
+    pub fn release_video_renderer(&self, plugin_id: u32, video_render_id: u32) -> Result<(), Error> {
+        call_suite_fn!(self, ReleaseVideoRenderer, plugin_id, video_render_id)
+    }
+    /// Create a video renderer for a timeline, optionally with a frame rate given as the duration of one frame.
+    ///
+    /// Returns the video render ID, which must be released with [`release_video_renderer_for_timeline()`](Self::release_video_renderer_for_timeline).
+    pub fn make_video_renderer_for_timeline(&self, timeline_id: pr_sys::PrTimelineID, frame_rate: Option<PrTime>) -> Result<u32, Error> {
+        match frame_rate {
+            Some(frame_rate) => call_suite_fn_single!(self, MakeVideoRendererForTimelineWithFrameRate -> u32, timeline_id, frame_rate.ticks()),
+            None             => call_suite_fn_single!(self, MakeVideoRendererForTimeline -> u32, timeline_id),
+        }
+    }
+    /// Release a video renderer created with [`make_video_renderer_for_timeline()`](Self::make_video_renderer_for_timeline).
+    pub fn release_video_renderer_for_timeline(&self, video_render_id: u32) -> Result<(), Error> {
+        call_suite_fn!(self, ReleaseVideoRendererForTimeline, video_render_id)
+    }
+
+    /// Render a video frame synchronously.
+    /// * `video_render_id` - The video renderer.
+    /// * `time` - The time of the frame in ticks.
+    /// * `params` - The render parameters.
+    /// * `cache_flags` - Which caches the render may use.
+    ///
+    /// Returns the rendered frame, which is disposed on drop.
+    pub fn render_video_frame(&self, video_render_id: u32, time: impl Into<PrTime>, params: &SequenceRenderParams, cache_flags: RenderCacheType) -> Result<RenderedFrame, Error> {
+        let time = time.into().ticks();
+        let formats = params.raw_pixel_formats();
+        let mut ret: SequenceRender_GetFrameReturnRec = unsafe { std::mem::zeroed() };
+        match params.color_space {
+            Some(color_space) => {
+                let mut raw = params.as_raw_ext(&formats, color_space);
+                call_suite_fn!(self, RenderColorManagedVideoFrame, video_render_id, time, &mut raw, cache_flags.bits(), &mut ret)?;
+            }
+            None => {
+                let mut raw = params.as_raw(&formats);
+                call_suite_fn!(self, RenderVideoFrame, video_render_id, time, &mut raw, cache_flags.bits(), &mut ret)?;
+            }
+        }
+        RenderedFrame::from_raw(&ret)
+    }
+
+    /// Render a video frame synchronously and convert it to `conform_to_format`.
+    ///
+    /// See [`render_video_frame()`](Self::render_video_frame).
+    pub fn render_video_frame_and_conform_to_pixel_format(&self, video_render_id: u32, time: impl Into<PrTime>, params: &SequenceRenderParams, cache_flags: RenderCacheType, conform_to_format: PixelFormat) -> Result<RenderedFrame, Error> {
+        let time = time.into().ticks();
+        let formats = params.raw_pixel_formats();
+        let mut ret: SequenceRender_GetFrameReturnRec = unsafe { std::mem::zeroed() };
+        match params.color_space {
+            Some(color_space) => {
+                let mut raw = params.as_raw_ext(&formats, color_space);
+                call_suite_fn!(self, RenderColorManagedVideoFrameAndConformToPixelFormat, video_render_id, time, &mut raw, cache_flags.bits(), conform_to_format.into(), &mut ret)?;
+            }
+            None => {
+                let mut raw = params.as_raw(&formats);
+                call_suite_fn!(self, RenderVideoFrameAndConformToPixelFormat, video_render_id, time, &mut raw, cache_flags.bits(), conform_to_format.into(), &mut ret)?;
+            }
+        }
+        RenderedFrame::from_raw(&ret)
+    }
+
+    /// Queue an asynchronous render of a video frame. The result is delivered to the completion proc of the renderer.
+    /// * `video_render_id` - The video renderer.
+    /// * `time` - The time of the frame in ticks.
+    /// * `params` - The render parameters.
+    /// * `cache_flags` - Which caches the render may use.
+    /// * `completion_data` - Passed back to the completion proc.
+    ///
+    /// Returns the request ID.
+    pub fn queue_async_video_frame_render(&self, video_render_id: u32, time: impl Into<PrTime>, params: &SequenceRenderParams, cache_flags: RenderCacheType, completion_data: *mut std::ffi::c_void) -> Result<u32, Error> {
+        let time = time.into().ticks();
+        let formats = params.raw_pixel_formats();
+        let mut request_id = 0;
+        match params.color_space {
+            Some(color_space) => {
+                let mut raw = params.as_raw_ext(&formats, color_space);
+                call_suite_fn!(self, QueueAsyncColorManagedVideoFrameRender, video_render_id, time, &mut request_id, &mut raw, cache_flags.bits(), completion_data)?;
+            }
+            None => {
+                let mut raw = params.as_raw(&formats);
+                call_suite_fn!(self, QueueAsyncVideoFrameRender, video_render_id, time, &mut request_id, &mut raw, cache_flags.bits(), completion_data)?;
+            }
+        }
+        Ok(request_id)
+    }
+
+    /// Set the raw completion proc for asynchronous renders. Prefer [`VideoRenderer::set_async_completion()`].
+    ///
+    /// # Safety
+    /// `callback_ref` must stay valid until the renderer is released.
+    pub unsafe fn set_async_render_completion_proc(&self, video_render_id: u32, callback: pr_sys::PrSDKSequenceAsyncRenderCompletionProc, callback_ref: *mut std::ffi::c_void) -> Result<(), Error> {
+        call_suite_fn!(self, SetAsyncRenderCompletionProc, video_render_id, callback, callback_ref)
+    }
+
+    /// Get information about the frame at `time`.
+    ///
+    /// Returns the number of subsequent frames which are identical to this one.
+    pub fn frame_info(&self, video_render_id: u32, time: impl Into<PrTime>) -> Result<i32, Error> {
+        let time = time.into().ticks();
+        Ok(call_suite_fn_single!(self, GetFrameInfo -> pr_sys::SequenceRender_FrameInfoRec, video_render_id, time)?.repeatCount)
+    }
+
+    /// Pre-fetches the media needed to render this frame.
+    /// This is a hint to the importers to begin reading media needed to render this video frame.
+    pub fn prefetch_media(&self, video_render_id: u32, time: impl Into<PrTime>) -> Result<(), Error> {
+        let time = time.into().ticks();
+        call_suite_fn!(self, PrefetchMedia, video_render_id, time)
+    }
+    /// Pre-fetches the media needed to render this frame, using all of the parameters used to render the frame.
+    /// This is a hint to the importers to begin reading media needed to render this video frame.
+    pub fn prefetch_media_with_render_parameters(&self, video_render_id: u32, time: impl Into<PrTime>, params: &SequenceRenderParams) -> Result<(), Error> {
+        let time = time.into().ticks();
+        let formats = params.raw_pixel_formats();
+        match params.color_space {
+            Some(color_space) => {
+                let mut raw = params.as_raw_ext(&formats, color_space);
+                call_suite_fn!(self, PrefetchColorManagedMediaWithRenderParameters, video_render_id, time, &mut raw)
+            }
+            None => {
+                let mut raw = params.as_raw(&formats);
+                call_suite_fn!(self, PrefetchMediaWithRenderParameters, video_render_id, time, &mut raw)
+            }
+        }
+    }
+    /// This will cancel all media pre-fetches that are still outstanding.
+    pub fn cancel_all_outstanding_media_prefetches(&self, video_render_id: u32) -> Result<(), Error> {
+        call_suite_fn!(self, CancelAllOutstandingMediaPrefetches, video_render_id)
+    }
+    /// Is all the prefetched media ready?
+    pub fn is_prefetched_media_ready(&self, video_render_id: u32, time: impl Into<PrTime>) -> Result<bool, Error> {
+        let time = time.into().ticks();
+        Ok(call_suite_fn_single!(self, IsPrefetchedMediaReady -> pr_sys::prBool, video_render_id, time)? != 0)
+    }
+}
+
+type AsyncCompletionFn = dyn Fn(AsyncRenderedFrame) + Send + Sync + 'static;
+
+struct AsyncCompletion {
+    pica_basic_suite_ptr: *const pr_sys::SPBasicSuite,
+    callback: Box<AsyncCompletionFn>,
+}
+
+unsafe extern "C" fn async_completion_proc(video_render_id: u32, callback_ref: *mut std::ffi::c_void, time: pr_sys::PrTime, rendered_frame: pr_sys::PPixHand, get_frame_return: *mut SequenceRender_GetFrameReturnRec) {
+    if callback_ref.is_null() {
+        return;
+    }
+    let completion = &*(callback_ref as *const AsyncCompletion);
+
+    // The completion proc is called on a host thread, so the suites have to be acquired from the pica suite saved at registration
+    let _pica = PicaBasicSuite::from_sp_basic_suite_raw(completion.pica_basic_suite_ptr);
+
+    let (tag, frame) = if get_frame_return.is_null() {
+        (0, OwnedPPix::from_raw(rendered_frame).map(|frame| RenderedFrame { frame, repeat_count: 0, on_marker: false }))
+    } else {
+        let ret = *get_frame_return;
+        let frame = match ret.returnVal {
+            0 => OwnedPPix::from_raw(rendered_frame).map(|frame| RenderedFrame {
+                frame,
+                repeat_count: ret.repeatCount,
+                on_marker: ret.onMarker != 0,
+            }),
+            err => Err(Error::from(err)),
+        };
+        (ret.asyncCompletionData as usize, frame)
+    };
+
+    (completion.callback)(AsyncRenderedFrame { video_render_id, time: PrTime(time), tag, frame });
+}
+
+/// A video renderer for a timeline or a plugin, released on drop.
+///
+/// ```ignore
+/// let renderer = VideoRenderer::for_timeline(filter.timeline_id(), None)?;
+/// let params = SequenceRenderParams::from_render_params(&render_params, &[PixelFormat::Bgra4444_32f]);
+/// let previous = renderer.render_frame(render_params.sequence_time() - render_params.render_ticks_per_frame(), &params, RenderCacheType::FRAMES)?;
+/// ```
+pub struct VideoRenderer {
+    suite: SequenceRenderSuite,
+    id: u32,
+    plugin_id: Option<u32>,
+    completion: Option<Box<AsyncCompletion>>,
+}
+
+impl VideoRenderer {
+    /// Create a video renderer for a plugin. See [`SequenceRenderSuite::make_video_renderer()`].
+    pub fn new(plugin_id: u32, frame_rate: impl Into<PrTime>) -> Result<Self, Error> {
+        let suite = SequenceRenderSuite::new()?;
+        let id = suite.make_video_renderer(plugin_id, frame_rate)?;
+        Ok(Self { suite, id, plugin_id: Some(plugin_id), completion: None })
+    }
+    /// Create a video renderer for a timeline. See [`SequenceRenderSuite::make_video_renderer_for_timeline()`].
+    pub fn for_timeline(timeline_id: pr_sys::PrTimelineID, frame_rate: Option<PrTime>) -> Result<Self, Error> {
+        let suite = SequenceRenderSuite::new()?;
+        let id = suite.make_video_renderer_for_timeline(timeline_id, frame_rate)?;
+        Ok(Self { suite, id, plugin_id: None, completion: None })
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Render a frame synchronously. See [`SequenceRenderSuite::render_video_frame()`].
+    pub fn render_frame(&self, time: impl Into<PrTime>, params: &SequenceRenderParams, cache_flags: RenderCacheType) -> Result<RenderedFrame, Error> {
+        let time = time.into().ticks();
+        self.suite.render_video_frame(self.id, time, params, cache_flags)
+    }
+    /// Render a frame synchronously and convert it to `format`. See [`SequenceRenderSuite::render_video_frame_and_conform_to_pixel_format()`].
+    pub fn render_frame_conformed(&self, time: impl Into<PrTime>, params: &SequenceRenderParams, cache_flags: RenderCacheType, format: PixelFormat) -> Result<RenderedFrame, Error> {
+        let time = time.into().ticks();
+        self.suite.render_video_frame_and_conform_to_pixel_format(self.id, time, params, cache_flags, format)
+    }
+
+    /// Set the closure that receives asynchronously rendered frames. It may be called on any thread.
+    ///
+    /// Replaces the previously set closure.
+    pub fn set_async_completion<F: Fn(AsyncRenderedFrame) + Send + Sync + 'static>(&mut self, callback: F) -> Result<(), Error> {
+        let completion = Box::new(AsyncCompletion {
+            pica_basic_suite_ptr: borrow_pica_basic_as_ptr(),
+            callback: Box::new(callback),
+        });
+        let callback_ref = &*completion as *const AsyncCompletion as *mut std::ffi::c_void;
+        unsafe { self.suite.set_async_render_completion_proc(self.id, Some(async_completion_proc), callback_ref)?; }
+        self.completion = Some(completion);
+        Ok(())
+    }
+
+    /// Queue an asynchronous frame render. The frame is delivered to the closure set with [`set_async_completion()`](Self::set_async_completion)
+    /// together with `tag`.
+    ///
+    /// Returns the request ID, or [`Error::RenderCompletionProcNotSet`] if no closure was set.
+    pub fn queue_async_frame_render(&self, time: impl Into<PrTime>, params: &SequenceRenderParams, cache_flags: RenderCacheType, tag: usize) -> Result<u32, Error> {
+        let time = time.into().ticks();
+        if self.completion.is_none() {
+            return Err(Error::RenderCompletionProcNotSet);
+        }
+        self.suite.queue_async_video_frame_render(self.id, time, params, cache_flags, tag as *mut std::ffi::c_void)
+    }
+
+    /// See [`SequenceRenderSuite::frame_info()`].
+    pub fn frame_info(&self, time: impl Into<PrTime>) -> Result<i32, Error> {
+        let time = time.into().ticks();
+        self.suite.frame_info(self.id, time)
+    }
+    /// See [`SequenceRenderSuite::prefetch_media()`].
+    pub fn prefetch_media(&self, time: impl Into<PrTime>) -> Result<(), Error> {
+        let time = time.into().ticks();
+        self.suite.prefetch_media(self.id, time)
+    }
+    /// See [`SequenceRenderSuite::prefetch_media_with_render_parameters()`].
+    pub fn prefetch_media_with_render_parameters(&self, time: impl Into<PrTime>, params: &SequenceRenderParams) -> Result<(), Error> {
+        let time = time.into().ticks();
+        self.suite.prefetch_media_with_render_parameters(self.id, time, params)
+    }
+    /// See [`SequenceRenderSuite::cancel_all_outstanding_media_prefetches()`].
+    pub fn cancel_all_outstanding_media_prefetches(&self) -> Result<(), Error> {
+        self.suite.cancel_all_outstanding_media_prefetches(self.id)
+    }
+    /// See [`SequenceRenderSuite::is_prefetched_media_ready()`].
+    pub fn is_prefetched_media_ready(&self, time: impl Into<PrTime>) -> Result<bool, Error> {
+        let time = time.into().ticks();
+        self.suite.is_prefetched_media_ready(self.id, time)
+    }
+}
+
+impl Drop for VideoRenderer {
+    fn drop(&mut self) {
+        let result = match self.plugin_id {
+            Some(plugin_id) => self.suite.release_video_renderer(plugin_id, self.id),
+            None            => self.suite.release_video_renderer_for_timeline(self.id),
+        };
+        if let Err(e) = result {
+            log::error!("Failed to release video renderer: {e:?}");
+        }
+        // The completion closure is dropped after the renderer is released, so no more callbacks can arrive
+        self.completion = None;
+    }
+}
@@ -32,8 +32,8 @@ impl TimeSuite {
     /// * `frame_rate` - an enum value for a video frame rate.
     ///
     /// Returns the number of time ticks per frame.
-    pub fn ticks_per_video_frame(&self, frame_rate: crate::VideoFrameRates) -> Result<pr_sys::PrTime, Error> {
-        call_suite_fn_single!(self, GetTicksPerVideoFrame -> pr_sys::PrTime, frame_rate.into())
+    pub fn ticks_per_video_frame(&self, frame_rate: crate::VideoFrameRates) -> Result<crate::PrTime, Error> {
+        call_suite_fn_single!(self, GetTicksPerVideoFrame -> pr_sys::PrTime, frame_rate.into()).map(crate::PrTime)
     }
 
     /// Get the number of ticks in an audio sample rate.
@@ -43,7 +43,7 @@ impl TimeSuite {
     ///
     /// Returns `kPrTimeSuite_RoundedAudioRate` if the requested audio sample rate is not an
     /// even divisor of the base tick count and therefore times in this rate will not be exact.
-    pub fn ticks_per_audio_sample(&self, sample_rate: f32) -> Result<pr_sys::PrTime, Error> {
-        call_suite_fn_single!(self, GetTicksPerAudioSample -> pr_sys::PrTime, sample_rate)
+    pub fn ticks_per_audio_sample(&self, sample_rate: f32) -> Result<crate::PrTime, Error> {
+        call_suite_fn_single!(self, GetTicksPerAudioSample -> pr_sys::PrTime, sample_rate).map(crate::PrTime)
     }
 }
@@ -37,7 +37,8 @@ pub struct VideoSegmentProperties {
     pub bounds: pr_sys::prRect,
     pub par_num: i32,
     pub par_den: i32,
-    pub frame_rate: i64,
+    /// Duration of a frame.
+    pub frame_rate: PrTime,
     pub field_type: pr_sys::prFieldType,
     pub color_space: Option<pr_sys::PrSDKColorSpaceID>,
 }
@@ -107,13 +108,13 @@ impl VideoSegmentSuite {
     /// * `end_time` - The end time of the segment
     /// * `segment_offset` - The offset value for the segment
     /// * `hash` - The hash for the segment
-    pub fn segment_info(&self, video_segments_id: i32, index: i32) -> Result<(i64, i64, i64, pr_sys::prPluginID), Error> {
+    pub fn segment_info(&self, video_segments_id: i32, index: i32) -> Result<(PrTime, PrTime, PrTime, pr_sys::prPluginID), Error> {
         let mut start_time = 0;
         let mut end_time = 0;
         let mut segment_offset = 0;
         let mut hash: pr_sys::prPluginID = unsafe { std::mem::zeroed() };
         call_suite_fn!(self, GetSegmentInfo, video_segments_id, index, &mut start_time, &mut end_time, &mut segment_offset, &mut hash)?;
-        Ok((start_time, end_time, segment_offset, hash))
+        Ok((start_time.into(), end_time.into(), segment_offset.into(), hash))
     }
 
     /// Get a segment node. This object is ref-counted and must be released.
@@ -141,8 +142,8 @@ impl VideoSegmentSuite {
         let mut node_type = [0; pr_sys::kMaxNodeTypeStringSize as usize];
         let mut hash: pr_sys::prPluginID = unsafe { std::mem::zeroed() };
         let mut flags = 0;
-        call_suite_fn!(self, GetNodeInfo, video_node_id, node_type.as_mut_ptr() as *mut i8, &mut hash, &mut flags)?;
-        Ok((String::from_utf8_lossy(&node_type).to_string(), hash, flags))
+        call_suite_fn!(self, GetNodeInfo, video_node_id, node_type.as_mut_ptr() as *mut std::ffi::c_char, &mut hash, &mut flags)?;
+        Ok((String::from_utf8_lossy(&node_type).trim_end_matches('\0').to_string(), hash, flags))
     }
 
     /// Get the number of inputs on the node object
@@ -160,8 +161,9 @@ impl VideoSegmentSuite {
     /// Returns a tuple containing:
     /// * `offset` - The time offset relative to it's parent node
     /// * `input_video_node_id` - The video node ID of the input node.
-    pub fn acquire_input_node_id(&self, video_node_id: i32, index: i32) -> Result<(i64, i32), Error> {
-        call_suite_fn_double!(self, AcquireInputNodeID -> i64, i32, video_node_id, index)
+    pub fn acquire_input_node_id(&self, video_node_id: i32, index: i32) -> Result<(PrTime, i32), Error> {
+        let (offset, input_node_id) = call_suite_fn_double!(self, AcquireInputNodeID -> i64, i32, video_node_id, index)?;
+        Ok((offset.into(), input_node_id))
     }
 
     /// Get the number of inputs on the node object
@@ -252,7 +254,8 @@ impl VideoSegmentSuite {
     /// * `time` - The time requested (in Media time)
     ///
     /// Returns the param
-    pub fn param(&self, video_node_id: i32, index: i32, time: i64) -> Result<crate::Param, Error> {
+    pub fn param(&self, video_node_id: i32, index: i32, time: impl Into<PrTime>) -> Result<crate::Param, Error> {
+        let time = time.into().ticks();
         Ok(call_suite_fn_single!(self, GetParam -> pr_sys::PrParam, video_node_id, index, time)?.into())
     }
 
@@ -271,11 +274,12 @@ impl VideoSegmentSuite {
     /// Returns a tuple containing:
     /// * `keyframe_time` - The time of the next keyframe > inTime
     /// * `keyframe_interpolation_mode` - The temporal interpolation mode of the keyframe
-    pub fn next_keyframe_time(&self, video_node_id: i32, index: i32, time: i64) -> Result<(i64, KeyframeInterpolationMode), Error> {
+    pub fn next_keyframe_time(&self, video_node_id: i32, index: i32, time: impl Into<PrTime>) -> Result<(PrTime, KeyframeInterpolationMode), Error> {
+        let time = time.into().ticks();
         let mut keyframe_time = 0;
         let mut keyframe_interpolation_mode: pr_sys::PrKeyframeInterpolationModeFlag = 0;
         call_suite_fn!(self, GetNextKeyframeTime, video_node_id, index, time, &mut keyframe_time, &mut keyframe_interpolation_mode as *mut pr_sys::PrKeyframeInterpolationModeFlag as _)?;
-        Ok((keyframe_time, keyframe_interpolation_mode.into()))
+        Ok((keyframe_time.into(), keyframe_interpolation_mode.into()))
     }
 
     /// Transform a node local time into a time appropriate for node inputs and
@@ -284,8 +288,9 @@ impl VideoSegmentSuite {
     /// which can be used in the Media and Effect nodes.
     /// If the node does not have a time transform, function will not fail but
     /// will return in input time in the output.
-    pub fn transform_node_time(&self, video_node_id: i32, time: i64) -> Result<i64, Error> {
-        call_suite_fn_single!(self, TransformNodeTime -> i64, video_node_id, time)
+    pub fn transform_node_time(&self, video_node_id: i32, time: impl Into<PrTime>) -> Result<PrTime, Error> {
+        let time = time.into().ticks();
+        call_suite_fn_single!(self, TransformNodeTime -> i64, video_node_id, time).map(PrTime)
     }
 
     /// Retrieve general properties of a sequence (video segments). I.e. width/height, par and framerate.
@@ -300,7 +305,7 @@ impl VideoSegmentSuite {
     /// * `field_type` - Field type of the sequence
     pub fn video_segments_properties(&self, timeline_data: pr_sys::PrTimelineID) -> Result<VideoSegmentProperties, Error> {
         let mut p: VideoSegmentProperties = unsafe { std::mem::zeroed() };
-        call_suite_fn!(self, GetVideoSegmentsProperties, timeline_data, &mut p.bounds, &mut p.par_num, &mut p.par_den, &mut p.frame_rate, &mut p.field_type)?;
+        call_suite_fn!(self, GetVideoSegmentsProperties, timeline_data, &mut p.bounds, &mut p.par_num, &mut p.par_den, &mut p.frame_rate.0, &mut p.field_type)?;
         Ok(p)
     }
     /// From a sequence, get a segment node for a requested time. This is a ref-counted
@@ -311,11 +316,12 @@ impl VideoSegmentSuite {
     /// Returns a tuple containing:
     /// * `video_node_id` - The video node ID
     /// * `segment_offset` - Offset of retrieved segment
-    pub fn acquire_node_for_time(&self, video_segments_id: i32, time: i64) -> Result<(i32, i64), Error> {
+    pub fn acquire_node_for_time(&self, video_segments_id: i32, time: impl Into<PrTime>) -> Result<(i32, PrTime), Error> {
+        let time = time.into().ticks();
         let mut video_node_id = 0;
         let mut segment_offset = 0;
         call_suite_fn!(self, AcquireNodeForTime, video_segments_id, time, &mut video_node_id, &mut segment_offset)?;
-        Ok((video_node_id, segment_offset))
+        Ok((video_node_id, segment_offset.into()))
     }
 
     /// From a sequence, get an ID to its video segments ID. This is a ref-counted
@@ -367,11 +373,13 @@ impl VideoSegmentSuite {
     /// Returns a tuple containing:
     /// * `video_node_id` - The video node ID
     /// * `segment_offset` - Offset of retrieved segment
-    pub fn acquire_first_node_in_time_range(&self, video_segments_id: i32, start_time: i64, end_time: i64) -> Result<(i32, i64), Error> {
+    pub fn acquire_first_node_in_time_range(&self, video_segments_id: i32, start_time: impl Into<PrTime>, end_time: impl Into<PrTime>) -> Result<(i32, PrTime), Error> {
+        let start_time = start_time.into().ticks();
+        let end_time = end_time.into().ticks();
         let mut video_node_id = 0;
         let mut segment_offset = 0;
         call_suite_fn!(self, AcquireFirstNodeInTimeRange, video_segments_id, start_time, end_time, &mut video_node_id, &mut segment_offset)?;
-        Ok((video_node_id, segment_offset))
+        Ok((video_node_id, segment_offset.into()))
     }
 
     /// Acquire the node owning an operator
@@ -389,7 +397,8 @@ impl VideoSegmentSuite {
     /// * `time` - The time requested (in Media time)
     ///
     /// Returns a tuple containing: (position, anchor, scale, rotation)
-    pub fn graphics_transformed_params(&self, video_node_id: i32, time: i64) -> Result<(pr_sys::prFPoint64, pr_sys::prFPoint64, pr_sys::prFPoint64, f32), Error> {
+    pub fn graphics_transformed_params(&self, video_node_id: i32, time: impl Into<PrTime>) -> Result<(pr_sys::prFPoint64, pr_sys::prFPoint64, pr_sys::prFPoint64, f32), Error> {
+        let time = time.into().ticks();
         let mut position = pr_sys::prFPoint64 { x: 0.0, y: 0.0 };
         let mut anchor = pr_sys::prFPoint64 { x: 0.0, y: 0.0 };
         let mut scale = pr_sys::prFPoint64 { x: 0.0, y: 0.0 };
@@ -425,7 +434,7 @@ impl VideoSegmentSuite {
     pub fn video_segments_properties_ext(&self, timeline_data: pr_sys::PrTimelineID) -> Result<VideoSegmentProperties, Error> {
         let mut p: VideoSegmentProperties = unsafe { std::mem::zeroed() };
         let mut color_space: pr_sys::PrSDKColorSpaceID = unsafe { std::mem::zeroed() };
-        call_suite_fn!(self, GetVideoSegmentsPropertiesExt, timeline_data, &mut p.bounds, &mut p.par_num, &mut p.par_den, &mut p.frame_rate, &mut p.field_type, &mut color_space)?;
+        call_suite_fn!(self, GetVideoSegmentsPropertiesExt, timeline_data, &mut p.bounds, &mut p.par_num, &mut p.par_den, &mut p.frame_rate.0, &mut p.field_type, &mut color_space)?;
         p.color_space = Some(color_space);
         Ok(p)
     }
@@ -441,13 +450,15 @@ impl VideoSegmentSuite {
     /// * `segment_start_time` - Start time of retrieved segment
     /// * `segment_end_time` - End time of retrieved segment
     /// * `segment_offset` - Offset of retrieved segment
-    pub fn acquire_first_node_in_time_range_ext(&self, video_segments_id: i32, start_time: i64, end_time: i64) -> Result<(i32, i64, i64, i64), Error> {
+    pub fn acquire_first_node_in_time_range_ext(&self, video_segments_id: i32, start_time: impl Into<PrTime>, end_time: impl Into<PrTime>) -> Result<(i32, PrTime, PrTime, PrTime), Error> {
+        let start_time = start_time.into().ticks();
+        let end_time = end_time.into().ticks();
         let mut video_node_id = 0;
         let mut segment_start_time = 0;
         let mut segment_end_time = 0;
         let mut segment_offset = 0;
         call_suite_fn!(self, AcquireFirstNodeInTimeRangeExt, video_segments_id, start_time, end_time, &mut video_node_id, &mut segment_start_time, &mut segment_end_time, &mut segment_offset)?;
-        Ok((video_node_id, segment_start_time, segment_end_time, segment_offset))
+        Ok((video_node_id, segment_start_time.into(), segment_end_time.into(), segment_offset.into()))
     }
 
     /// Returns the relative time rate of a node at a given point in time.
@@ -457,7 +468,8 @@ impl VideoSegmentSuite {
     /// * `time` - The time requested (in Media time - untransformed)
     ///
     /// Returns the node rate relative to the containing sequence
-    pub fn node_time_scale(&self, video_node_id: i32, time: i64) -> Result<f64, Error> {
+    pub fn node_time_scale(&self, video_node_id: i32, time: impl Into<PrTime>) -> Result<f64, Error> {
+        let time = time.into().ticks();
         call_suite_fn_single!(self, GetNodeTimeScale -> f64, video_node_id, time)
     }
 }
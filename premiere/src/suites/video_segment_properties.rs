@@ -16,7 +16,7 @@ pub enum PropertyData {
     String(String),
     Binary(Binary),
     Point32(Point32),
-    Time(PrTime),
+    Time(crate::PrTime),
     Keyframes(Keyframes),
     Unknown(String),
 }
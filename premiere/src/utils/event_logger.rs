@@ -0,0 +1,119 @@
+use crate::*;
+use crate::suites::error::EventType;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::time::{ Duration, Instant };
+
+/// A [`log::Log`] implementation which forwards records to the Premiere Events panel using [`suites::Error`].
+///
+/// Every distinct message is reported at most once per `rate_limit`, so errors logged for every rendered frame don't flood the panel.
+/// Records are also forwarded when logged from threads where the host didn't provide the PICA basic suite, using the pointer captured in [`EventLogger::new()`].
+///
+/// ```ignore
+/// premiere::utils::EventLogger::new("My Plugin").init().ok();
+/// log::error!("Failed to allocate GPU memory: {err:?}");
+/// ```
+pub struct EventLogger {
+    title: String,
+    level: log::LevelFilter,
+    rate_limit: Duration,
+    pica_basic_suite_ptr: usize,
+    last_reported: Mutex<HashMap<String, Instant>>,
+}
+
+impl EventLogger {
+    const MAX_TRACKED_MESSAGES: usize = 1024;
+
+    /// Create a logger which reports events with the given title.
+    ///
+    /// Must be called from a host callback, so the PICA basic suite is available.
+    /// By default, only `warn!` and `error!` records are forwarded and every message is reported at most once every 5 seconds.
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_owned(),
+            level: log::LevelFilter::Warn,
+            rate_limit: Duration::from_secs(5),
+            pica_basic_suite_ptr: borrow_pica_basic_as_ptr() as usize,
+            last_reported: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Forward records up to this level. `Info` and lower levels are reported as informational events.
+    pub fn with_level(mut self, level: log::LevelFilter) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Report the same message at most once per `rate_limit`.
+    pub fn with_rate_limit(mut self, rate_limit: Duration) -> Self {
+        self.rate_limit = rate_limit;
+        self
+    }
+
+    /// Install this logger as the global logger and set the max log level.
+    pub fn init(self) -> Result<(), log::SetLoggerError> {
+        let level = self.level;
+        log::set_logger(Box::leak(Box::new(self)))?;
+        log::set_max_level(level);
+        Ok(())
+    }
+
+    fn should_report(&self, message: &str) -> bool {
+        let now = Instant::now();
+        let mut last_reported = self.last_reported.lock();
+        if let Some(last) = last_reported.get(message) {
+            if now.duration_since(*last) < self.rate_limit {
+                return false;
+            }
+        }
+        if last_reported.len() >= Self::MAX_TRACKED_MESSAGES {
+            last_reported.retain(|_, last| now.duration_since(*last) < self.rate_limit);
+        }
+        last_reported.insert(message.to_owned(), now);
+        true
+    }
+}
+
+impl log::Log for EventLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let message = record.args().to_string();
+        if !self.should_report(&message) {
+            return;
+        }
+        let event_type = match record.level() {
+            log::Level::Error => EventType::Error,
+            log::Level::Warn  => EventType::Warning,
+            _                 => EventType::Informational,
+        };
+
+        let _pica = if borrow_pica_basic_as_ptr().is_null() {
+            Some(PicaBasicSuite::from_sp_basic_suite_raw(self.pica_basic_suite_ptr as *const _))
+        } else {
+            None
+        };
+        // There's nowhere to report a failure of the logger itself
+        if let Ok(suite) = suites::Error::new() {
+            let _ = suite.set_event_string(event_type, &self.title, &message);
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+#[test]
+fn test_rate_limit() {
+    let logger = EventLogger::new("test").with_rate_limit(Duration::from_secs(60));
+    assert!(logger.should_report("a"));
+    assert!(!logger.should_report("a"));
+    assert!(logger.should_report("b"));
+
+    let logger = logger.with_rate_limit(Duration::ZERO);
+    assert!(logger.should_report("a"));
+}
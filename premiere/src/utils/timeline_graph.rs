@@ -0,0 +1,173 @@
+use crate::*;
+use serde::{ Deserialize, Serialize };
+
+/// A typed model of a sequence, built by [`VideoSequenceParser::parse_timeline()`](super::VideoSequenceParser::parse_timeline).
+///
+/// The tree mirrors the Video Segment suite: a timeline is split into segments, each segment has a root [`Node`],
+/// and every node has input nodes and operator nodes (effects). Nodes carry their parameters with keyframes.
+///
+/// Hashes are the GUIDs reported by Premiere. If the hash of a segment didn't change, its contents didn't change either,
+/// which is used by [`changed_segments()`](Self::changed_segments) and to skip re-parsing unchanged segments.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Timeline {
+    pub hash: String,
+    pub segments: Vec<Segment>,
+}
+
+/// A time range of the timeline in which the node graph doesn't change.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Segment {
+    /// Start time of the segment, in sequence time.
+    pub start_time: PrTime,
+    /// End time of the segment, in sequence time.
+    pub end_time: PrTime,
+    pub offset: PrTime,
+    pub hash: String,
+    /// The root node of the segment, `None` if the host didn't return a node for this segment.
+    pub node: Option<Node>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NodeKind {
+    Clip,
+    Compositor,
+    Disabled,
+    Effect,
+    Transition,
+    Media,
+    Preview,
+    SolidColor,
+    Multicam,
+    Adjustment,
+    AdjustmentEffect,
+    Other(String),
+}
+
+impl NodeKind {
+    pub fn from_node_type(node_type: &str) -> Self {
+        let is = |x: &[u8]| x.strip_suffix(b"\0").unwrap_or(x) == node_type.as_bytes();
+        if      is(pr_sys::kVideoSegment_NodeType_Clip)             { Self::Clip }
+        else if is(pr_sys::kVideoSegment_NodeType_Compositor)       { Self::Compositor }
+        else if is(pr_sys::kVideoSegment_NodeType_Disabled)         { Self::Disabled }
+        else if is(pr_sys::kVideoSegment_NodeType_Effect)           { Self::Effect }
+        else if is(pr_sys::kVideoSegment_NodeType_Transition)       { Self::Transition }
+        else if is(pr_sys::kVideoSegment_NodeType_Media)            { Self::Media }
+        else if is(pr_sys::kVideoSegment_NodeType_Preview)          { Self::Preview }
+        else if is(pr_sys::kVideoSegment_NodeType_SolidColor)       { Self::SolidColor }
+        else if is(pr_sys::kVideoSegment_NodeType_Multicam)         { Self::Multicam }
+        else if is(pr_sys::kVideoSegment_NodeType_Adjustment)       { Self::Adjustment }
+        else if is(pr_sys::kVideoSegment_NodeType_AdjustmentEffect) { Self::AdjustmentEffect }
+        else { Self::Other(node_type.to_owned()) }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Node {
+    pub kind: NodeKind,
+    pub hash: String,
+    pub flags: i32,
+    /// Match name of the effect or transition, if this node is one.
+    pub match_name: Option<String>,
+    /// Runtime instance ID of the effect, if this node is one.
+    pub instance_id: Option<u32>,
+    pub inputs: Vec<NodeInput>,
+    pub operators: Vec<Node>,
+    pub params: Vec<NodeParam>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeInput {
+    /// Time offset relative to the parent node.
+    pub offset: PrTime,
+    pub node: Node,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NodeParam {
+    pub index: i32,
+    /// Value at the start of the segment, in the same time base as the keyframes.
+    pub value: ParamValue,
+    /// Empty if the parameter isn't animated.
+    pub keyframes: Vec<Keyframe>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Keyframe {
+    /// Time of the keyframe, in the time of the node's inputs and operators,
+    /// which is the media time for clips. See [`VideoSegment::transform_node_time()`](crate::suites::VideoSegment::transform_node_time).
+    pub time: PrTime,
+    pub value: ParamValue,
+    #[serde(with = "interpolation_serde")]
+    pub interpolation: KeyframeInterpolationMode,
+}
+
+/// An owned, serializable version of [`Param`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ParamValue {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Point { x: f64, y: f64 },
+    Guid(String),
+    /// Parameter types which can't be represented, like memory pointers.
+    Unsupported,
+}
+
+impl From<Param> for ParamValue {
+    fn from(param: Param) -> Self {
+        match param {
+            Param::Int8(x)      => Self::Int(x as _),
+            Param::Int16(x)     => Self::Int(x as _),
+            Param::Int32(x)     => Self::Int(x as _),
+            Param::Int64(x)     => Self::Int(x),
+            Param::Float32(x)   => Self::Float(x as _),
+            Param::Float64(x)   => Self::Float(x),
+            Param::Bool(x)      => Self::Bool(x),
+            Param::Point(x)     => Self::Point { x: x.x, y: x.y },
+            Param::Guid(x)      => Self::Guid(guid_to_string(&x)),
+            Param::MemoryPtr(_) => Self::Unsupported,
+        }
+    }
+}
+
+impl Timeline {
+    /// Returns the segment which contains `time`.
+    pub fn segment_at(&self, time: impl Into<PrTime>) -> Option<&Segment> {
+        let time = time.into();
+        self.segments.iter().find(|x| time >= x.start_time && time < x.end_time)
+    }
+
+    /// Returns the segments which are new or different compared to `previous`.
+    pub fn changed_segments<'a>(&'a self, previous: &Timeline) -> impl Iterator<Item = &'a Segment> + 'a {
+        let previous_hashes: std::collections::HashSet<String> = previous.segments.iter().map(|x| x.hash.clone()).collect();
+        self.segments.iter().filter(move |x| !previous_hashes.contains(&x.hash))
+    }
+}
+
+pub(crate) fn guid_to_string(id: &pr_sys::prPluginID) -> String {
+    let bytes: Vec<u8> = id.mGUID.iter().take_while(|&&x| x != 0).map(|&x| x as u8).collect();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+mod interpolation_serde {
+    use super::*;
+    use serde::{ Deserializer, Serializer };
+
+    pub fn serialize<S: Serializer>(mode: &KeyframeInterpolationMode, serializer: S) -> Result<S::Ok, S::Error> {
+        let raw: pr_sys::PrKeyframeInterpolationModeFlag = (*mode).into();
+        serializer.serialize_u32(raw as u32)
+    }
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<KeyframeInterpolationMode, D::Error> {
+        let raw = u32::deserialize(deserializer)?;
+        Ok((raw as pr_sys::PrKeyframeInterpolationModeFlag).into())
+    }
+}
+
+#[test]
+fn test_changed_segments() {
+    let segment = |hash: &str| Segment { start_time: PrTime(0), end_time: PrTime(10), offset: PrTime::ZERO, hash: hash.to_owned(), node: None };
+    let previous = Timeline { hash: "a".into(), segments: vec![segment("1"), segment("2")] };
+    let current  = Timeline { hash: "b".into(), segments: vec![segment("1"), segment("3")] };
+    let changed: Vec<_> = current.changed_segments(&previous).map(|x| x.hash.as_str()).collect();
+    assert_eq!(changed, ["3"]);
+}
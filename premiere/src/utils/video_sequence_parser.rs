@@ -1,6 +1,8 @@
 use crate::*;
 use pr_sys::*;
 use std::collections::HashMap;
+use crate::PrTime;
+use super::timeline_graph::*;
 
 #[derive(Debug, Clone)]
 pub struct ClipOperator {
@@ -42,9 +44,7 @@ impl VideoSequenceParser {
                 operator_node_flags
             );
 
-            if operator_node_type
-                == String::from_utf8_lossy(kVideoSegment_NodeType_Effect).to_string()
-            {
+            if NodeKind::from_node_type(&operator_node_type) == NodeKind::Effect {
                 let effect_name = self
                     .segment_suite
                     .node_property(operator_node_id, Property::Effect_FilterMatchName)
@@ -76,4 +76,229 @@ impl VideoSequenceParser {
 
         Ok(operators_map)
     }
+
+    /// Build a typed model of the whole timeline.
+    /// * `timeline_id` - The timeline to parse.
+    /// * `previous` - A previously parsed version of the same timeline. Segments with unchanged hashes are reused from it instead of walking their node graph again.
+    pub fn parse_timeline(&self, timeline_id: PrTimelineID, previous: Option<&Timeline>) -> Result<Timeline, Error> {
+        let segments_id = self.segment_suite.acquire_video_segments_id(timeline_id)?;
+        let result = self.parse_segments(segments_id, previous);
+        self.segment_suite.release_video_segments_id(segments_id)?;
+        result
+    }
+
+    fn parse_segments(&self, segments_id: i32, previous: Option<&Timeline>) -> Result<Timeline, Error> {
+        let hash = guid_to_string(&self.segment_suite.hash(segments_id)?);
+        if let Some(previous) = previous.filter(|x| x.hash == hash) {
+            return Ok(previous.clone());
+        }
+
+        let count = self.segment_suite.segment_count(segments_id)?;
+        let mut segments = Vec::with_capacity(count.max(0) as usize);
+        for index in 0..count {
+            let (start_time, end_time, offset, mut segment_hash) = self.segment_suite.segment_info(segments_id, index)?;
+            let hash = guid_to_string(&segment_hash);
+
+            let unchanged = previous.and_then(|x| x.segments.iter().find(|x| x.hash == hash));
+            let node = if let Some(unchanged) = unchanged {
+                unchanged.node.clone()
+            } else {
+                match self.segment_suite.acquire_node_id(segments_id, &mut segment_hash) {
+                    Ok(node_id) => {
+                        let node = parse_node(&self.segment_suite, node_id, start_time);
+                        self.segment_suite.release_video_node_id(node_id)?;
+                        Some(node?)
+                    }
+                    Err(e) => {
+                        log::debug!("No node for segment {index}: {e:?}");
+                        None
+                    }
+                }
+            };
+            segments.push(Segment { start_time, end_time, offset, hash, node });
+        }
+
+        Ok(Timeline { hash, segments })
+    }
+}
+
+/// The calls of the Video Segment suite used to walk a node graph.
+trait NodeGraph {
+    fn node_info(&self, node_id: i32) -> Result<(String, prPluginID, i32), Error>;
+    fn node_property(&self, node_id: i32, key: Property) -> Result<PropertyData, Error>;
+    fn transform_node_time(&self, node_id: i32, time: PrTime) -> Result<PrTime, Error>;
+    fn node_input_count(&self, node_id: i32) -> Result<i32, Error>;
+    fn acquire_input_node_id(&self, node_id: i32, index: i32) -> Result<(PrTime, i32), Error>;
+    fn node_operator_count(&self, node_id: i32) -> Result<i32, Error>;
+    fn acquire_operator_node_id(&self, node_id: i32, index: i32) -> Result<i32, Error>;
+    fn release_video_node_id(&self, node_id: i32) -> Result<(), Error>;
+    fn param_count(&self, node_id: i32) -> Result<i32, Error>;
+    fn param(&self, node_id: i32, index: i32, time: PrTime) -> Result<Param, Error>;
+    fn next_keyframe_time(&self, node_id: i32, index: i32, time: PrTime) -> Result<(PrTime, KeyframeInterpolationMode), Error>;
+}
+
+impl NodeGraph for suites::VideoSegment {
+    fn node_info(&self, node_id: i32) -> Result<(String, prPluginID, i32), Error> { self.node_info(node_id) }
+    fn node_property(&self, node_id: i32, key: Property) -> Result<PropertyData, Error> { self.node_property(node_id, key) }
+    fn transform_node_time(&self, node_id: i32, time: PrTime) -> Result<PrTime, Error> { self.transform_node_time(node_id, time) }
+    fn node_input_count(&self, node_id: i32) -> Result<i32, Error> { self.node_input_count(node_id) }
+    fn acquire_input_node_id(&self, node_id: i32, index: i32) -> Result<(PrTime, i32), Error> { self.acquire_input_node_id(node_id, index) }
+    fn node_operator_count(&self, node_id: i32) -> Result<i32, Error> { self.node_operator_count(node_id) }
+    fn acquire_operator_node_id(&self, node_id: i32, index: i32) -> Result<i32, Error> { self.acquire_operator_node_id(node_id, index) }
+    fn release_video_node_id(&self, node_id: i32) -> Result<(), Error> { self.release_video_node_id(node_id) }
+    fn param_count(&self, node_id: i32) -> Result<i32, Error> { self.param_count(node_id) }
+    fn param(&self, node_id: i32, index: i32, time: PrTime) -> Result<Param, Error> { self.param(node_id, index, time) }
+    fn next_keyframe_time(&self, node_id: i32, index: i32, time: PrTime) -> Result<(PrTime, KeyframeInterpolationMode), Error> { self.next_keyframe_time(node_id, index, time) }
+}
+
+/// Parse a node with its inputs, operators and parameters.
+/// * `time` - Time of the node as seen by its parent. For the root node of a segment, this is the start of the segment in sequence time.
+fn parse_node(graph: &impl NodeGraph, node_id: i32, time: PrTime) -> Result<Node, Error> {
+    let (node_type, hash, flags) = graph.node_info(node_id)?;
+    let kind = NodeKind::from_node_type(&node_type);
+
+    let (match_name, instance_id) = if matches!(kind, NodeKind::Effect | NodeKind::AdjustmentEffect | NodeKind::Transition) {
+        let match_name = match graph.node_property(node_id, Property::Effect_FilterMatchName) {
+            Ok(PropertyData::String(x)) => Some(x),
+            _ => None,
+        };
+        let instance_id = match graph.node_property(node_id, Property::Effect_RuntimeInstanceID) {
+            Ok(PropertyData::UInt32(x)) => Some(x),
+            _ => None,
+        };
+        (match_name, instance_id)
+    } else {
+        (None, None)
+    };
+
+    // Inputs, operators and parameters work in the transformed time of this node, after speed changes and time remapping,
+    // so the values and keyframes are in the same time base in which the effects see them
+    let local_time = graph.transform_node_time(node_id, time).unwrap_or(time);
+
+    let mut inputs = Vec::new();
+    for index in 0..graph.node_input_count(node_id)? {
+        let (offset, input_id) = graph.acquire_input_node_id(node_id, index)?;
+        let node = parse_node(graph, input_id, local_time);
+        graph.release_video_node_id(input_id)?;
+        inputs.push(NodeInput { offset, node: node? });
+    }
+
+    let mut operators = Vec::new();
+    for index in 0..graph.node_operator_count(node_id)? {
+        let operator_id = graph.acquire_operator_node_id(node_id, index)?;
+        let node = parse_node(graph, operator_id, local_time);
+        graph.release_video_node_id(operator_id)?;
+        operators.push(node?);
+    }
+
+    let mut params = Vec::new();
+    for index in 0..graph.param_count(node_id).unwrap_or(0) {
+        let value = graph.param(node_id, index, local_time)?.into();
+
+        let mut keyframes = Vec::new();
+        let mut keyframe_time = PrTime(i64::MIN);
+        while let Ok((next_time, interpolation)) = graph.next_keyframe_time(node_id, index, keyframe_time) {
+            if next_time <= keyframe_time {
+                break;
+            }
+            keyframes.push(Keyframe {
+                time: next_time,
+                value: graph.param(node_id, index, next_time)?.into(),
+                interpolation,
+            });
+            keyframe_time = next_time;
+        }
+        params.push(NodeParam { index, value, keyframes });
+    }
+
+    Ok(Node {
+        kind,
+        hash: guid_to_string(&hash),
+        flags,
+        match_name,
+        instance_id,
+        inputs,
+        operators,
+        params,
+    })
+}
+
+#[test]
+fn test_parse_node() {
+    use std::cell::Cell;
+
+    struct TestNode {
+        node_type: &'static [u8],
+        // Added by `transform_node_time`, like a clip which starts later in its media
+        time_offset: i64,
+        inputs: Vec<i32>,
+        operators: Vec<i32>,
+        // Keyframes of a single parameter
+        keyframes: Vec<(i64, f64)>,
+    }
+    struct TestGraph {
+        nodes: HashMap<i32, TestNode>,
+        acquired: Cell<i32>,
+    }
+    impl NodeGraph for TestGraph {
+        fn node_info(&self, node_id: i32) -> Result<(String, prPluginID, i32), Error> {
+            let node_type = self.nodes[&node_id].node_type;
+            let node_type = String::from_utf8_lossy(node_type.strip_suffix(b"\0").unwrap_or(node_type)).into_owned();
+            Ok((node_type, unsafe { std::mem::zeroed() }, 0))
+        }
+        fn node_property(&self, _: i32, _: Property) -> Result<PropertyData, Error> { Err(Error::Fail) }
+        fn transform_node_time(&self, node_id: i32, time: PrTime) -> Result<PrTime, Error> { Ok(time + PrTime(self.nodes[&node_id].time_offset)) }
+        fn node_input_count(&self, node_id: i32) -> Result<i32, Error> { Ok(self.nodes[&node_id].inputs.len() as _) }
+        fn acquire_input_node_id(&self, node_id: i32, index: i32) -> Result<(PrTime, i32), Error> {
+            self.acquired.set(self.acquired.get() + 1);
+            Ok((PrTime::ZERO, self.nodes[&node_id].inputs[index as usize]))
+        }
+        fn node_operator_count(&self, node_id: i32) -> Result<i32, Error> { Ok(self.nodes[&node_id].operators.len() as _) }
+        fn acquire_operator_node_id(&self, node_id: i32, index: i32) -> Result<i32, Error> {
+            self.acquired.set(self.acquired.get() + 1);
+            Ok(self.nodes[&node_id].operators[index as usize])
+        }
+        fn release_video_node_id(&self, _: i32) -> Result<(), Error> {
+            self.acquired.set(self.acquired.get() - 1);
+            Ok(())
+        }
+        fn param_count(&self, node_id: i32) -> Result<i32, Error> { Ok(if self.nodes[&node_id].keyframes.is_empty() { 0 } else { 1 }) }
+        fn param(&self, node_id: i32, _: i32, time: PrTime) -> Result<Param, Error> {
+            // Hold the value of the last keyframe
+            let keyframes = &self.nodes[&node_id].keyframes;
+            Ok(Param::Float64(keyframes.iter().rev().find(|x| x.0 <= time.ticks()).map_or(0.0, |x| x.1)))
+        }
+        fn next_keyframe_time(&self, node_id: i32, _: i32, time: PrTime) -> Result<(PrTime, KeyframeInterpolationMode), Error> {
+            let keyframes = &self.nodes[&node_id].keyframes;
+            keyframes.iter().find(|x| x.0 > time.ticks()).map(|x| (PrTime(x.0), KeyframeInterpolationMode::Hold)).ok_or(Error::NoKeyframeAfterInTime)
+        }
+    }
+
+    let node = |node_type, time_offset, inputs, operators, keyframes| TestNode { node_type, time_offset, inputs, operators, keyframes };
+    let graph = TestGraph {
+        nodes: HashMap::from([
+            (1, node(kVideoSegment_NodeType_Clip,   1000, vec![2], vec![3], vec![(1000, 1.0), (2000, 2.0)])),
+            (2, node(kVideoSegment_NodeType_Media,  0,    vec![],  vec![],  vec![])),
+            (3, node(kVideoSegment_NodeType_Effect, 0,    vec![],  vec![],  vec![(500, 5.0), (1500, 15.0)])),
+        ]),
+        acquired: Cell::new(0),
+    };
+
+    // The segment starts at 0 in sequence time, which is 1000 in the time of the clip's inputs and operators
+    let clip = parse_node(&graph, 1, PrTime::ZERO).unwrap();
+    assert_eq!(graph.acquired.get(), 0);
+    assert_eq!(clip.kind, NodeKind::Clip);
+    assert_eq!(clip.inputs.len(), 1);
+    assert_eq!(clip.inputs[0].node.kind, NodeKind::Media);
+
+    // The values are read in the same time base as the keyframes
+    assert_eq!(clip.params[0].value, ParamValue::Float(1.0));
+    let keyframes: Vec<_> = clip.params[0].keyframes.iter().map(|x| (x.time.ticks(), x.value.clone())).collect();
+    assert_eq!(keyframes, [(1000, ParamValue::Float(1.0)), (2000, ParamValue::Float(2.0))]);
+
+    let effect = &clip.operators[0];
+    assert_eq!(effect.kind, NodeKind::Effect);
+    assert_eq!(effect.params[0].value, ParamValue::Float(5.0));
+    let keyframes: Vec<_> = effect.params[0].keyframes.iter().map(|x| x.time.ticks()).collect();
+    assert_eq!(keyframes, [500, 1500]);
 }